@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use crate::config::BankConfig;
+use crate::ledger::{Category, Ledger};
+
+/// Money borrowed from the bank, paid back in equal instalments at the end of each day.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Loan {
+    pub day_taken: u32,
+    pub principal: f32,
+    /// What's still owed, not counting interest to come.
+    pub balance: f32,
+    pub daily_rate: f32,
+    /// Paid every day until the loan is cleared, interest first.
+    pub daily_payment: f32,
+    pub payments_left: u32,
+}
+
+impl Loan {
+    /// A loan of `amount` on the bank's current terms, with the same payment
+    /// every day so that the last one clears it.
+    pub fn new(day_taken: u32, amount: f32, config: &BankConfig) -> Self {
+        let rate = config.loan_daily_rate;
+        let days = config.loan_term_days;
+        let daily_payment = if rate == 0.0 {
+            amount / days as f32
+        } else {
+            amount * rate / (1.0 - (1.0 + rate).powi(-(days as i32)))
+        };
+        Self { day_taken, principal: amount, balance: amount, daily_rate: rate, daily_payment, payments_left: days }
+    }
+
+    /// Make one day's payment, returning how much of it was interest and how much paid off the loan.
+    fn pay_day(&mut self) -> (f32, f32) {
+        let interest = self.balance * self.daily_rate;
+        let principal = if self.payments_left <= 1 {
+            self.balance
+        } else {
+            (self.daily_payment - interest).clamp(0.0, self.balance)
+        };
+        self.balance -= principal;
+        self.payments_left = self.payments_left.saturating_sub(1);
+        (interest, principal)
+    }
+
+    pub fn is_paid_off(&self) -> bool { self.payments_left == 0 || self.balance <= 0.0 }
+}
+
+/// The business's dealings with the bank: the loans it still owes on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Bank {
+    loans: Vec<Loan>,
+}
+
+impl Bank {
+    pub fn with_loans(mut self, loans: Vec<Loan>) -> Self { self.loans = loans; self }
+
+    pub fn loans(&self) -> &[Loan] { &self.loans }
+
+    /// The total still to pay back on every loan.
+    pub fn owed(&self) -> f32 { self.loans.iter().fold(0.0, |owed, loan| owed + loan.balance) }
+
+    /// What the loans cost each day between them.
+    pub fn daily_payments(&self) -> f32 { self.loans.iter().fold(0.0, |paid, loan| paid + loan.daily_payment) }
+
+    /// The most the bank will lend in total, loans and overdraft together. It
+    /// starts at `base_credit` and grows with the rental takings over the last
+    /// `credit_history_days` days before `day`.
+    pub fn credit_limit(&self, ledger: &Ledger, day: u32, config: &BankConfig) -> f32 {
+        let since = day.saturating_sub(config.credit_history_days);
+        let takings: f32 = ledger.transactions().iter()
+            .filter(|transaction| transaction.category == Category::Rentals)
+            .filter(|transaction| transaction.day >= since && transaction.day < day)
+            .map(|transaction| transaction.amount)
+            .sum();
+        config.base_credit + config.revenue_multiple * takings.max(0.0)
+    }
+
+    pub fn borrow(&mut self, day: u32, amount: f32, config: &BankConfig) -> Loan {
+        let loan = Loan::new(day, amount, config);
+        self.loans.push(loan);
+        loan
+    }
+
+    /// Pay `amount` off the oldest loans first, returning what was actually paid.
+    pub fn repay(&mut self, amount: f32) -> f32 {
+        let mut left = amount;
+        for loan in self.loans.iter_mut() {
+            let paid = left.min(loan.balance);
+            loan.balance -= paid;
+            left -= paid;
+        }
+        self.loans.retain(|loan| loan.balance > 0.0);
+        amount - left
+    }
+
+    /// Make every loan's payment for the day, returning the interest and the
+    /// principal paid between them. Loans paid off are forgotten.
+    pub fn pay_day(&mut self) -> (f32, f32) {
+        let paid = self.loans.iter_mut()
+            .map(|loan| loan.pay_day())
+            .fold((0.0, 0.0), |(interest, principal), (i, p)| (interest + i, principal + p));
+        self.loans.retain(|loan| !loan.is_paid_off());
+        paid
+    }
+}
+
+#[cfg(test)]
+mod bank_tests {
+    use crate::bank::{Bank, Loan};
+    use crate::config::BankConfig;
+    use crate::ledger::{Category, Ledger};
+
+    fn config() -> BankConfig {
+        BankConfig { loan_daily_rate: 0.01, loan_term_days: 10, ..BankConfig::default() }
+    }
+
+    #[test]
+    fn a_loan_is_cleared_by_its_last_payment() {
+        let mut bank = Bank::default();
+        let loan = bank.borrow(1, 100.0, &config());
+        assert!(loan.daily_payment > 10.0);
+        let mut interest = 0.0;
+        let mut principal = 0.0;
+        for _ in 0..10 {
+            let (i, p) = bank.pay_day();
+            interest += i;
+            principal += p;
+        }
+        assert!(bank.loans().is_empty());
+        assert!((principal - 100.0).abs() < 0.01);
+        assert!((interest + principal - loan.daily_payment * 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn interest_free_loans_split_evenly() {
+        let loan = Loan::new(1, 50.0, &BankConfig { loan_daily_rate: 0.0, loan_term_days: 5, ..BankConfig::default() });
+        assert_eq!(loan.daily_payment, 10.0);
+    }
+
+    #[test]
+    fn repaying_early_clears_the_oldest_loan_first() {
+        let mut bank = Bank::default();
+        bank.borrow(1, 100.0, &config());
+        bank.borrow(2, 50.0, &config());
+        assert_eq!(bank.repay(120.0), 120.0);
+        assert_eq!(bank.loans().len(), 1);
+        assert_eq!(bank.owed(), 30.0);
+        assert_eq!(bank.repay(100.0), 30.0);
+        assert!(bank.loans().is_empty());
+    }
+
+    #[test]
+    fn credit_grows_with_recent_takings() {
+        let config = BankConfig { base_credit: 100.0, revenue_multiple: 2.0, credit_history_days: 3, ..config() };
+        let mut ledger = Ledger::opening(1, 100.0);
+        ledger.record(1, Category::Rentals, 40.0);
+        ledger.record(4, Category::Rentals, 30.0);
+        ledger.record(5, Category::Rentals, 1000.0);
+        let bank = Bank::default();
+        assert_eq!(bank.credit_limit(&ledger, 2, &config), 180.0);
+        // day 1 has dropped out of the window, and day 5 hasn't finished
+        assert_eq!(bank.credit_limit(&ledger, 5, &config), 160.0);
+    }
+}
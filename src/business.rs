@@ -1,234 +1,541 @@
-use std::cmp::{max, min};
-use rand::{random, Rng};
-use crate::{ADVERT_EFFECT, CHANCE_SCOOTER_BREAKS, OPTIMAL_RENTAL_PRICE, STARTING_CASH, STARTING_SCOOTERS};
-use crate::weather::{Temperature, WeatherType};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::bank::{Bank, Loan};
+use crate::config::{BankConfig, FleetConfig, GameConfig, OperatingConfig};
+use crate::demand::{DemandContext, DemandModel, Segments};
+use crate::fleet::Fleet;
+use crate::ledger::{Category, Ledger};
+use crate::location::{Location, LocationConfig, Locations, Shop};
+use crate::period::{Period, Periods};
+use crate::weather::{Weather, WeatherType};
 
 type DidItWork = Result<(), ManagementError>;
 
 #[derive(PartialEq, Debug)]
-pub(crate) enum ManagementError {
+pub enum ManagementError {
     InsufficientWorkingScooters,
     InsufficientBrokenScooters,
     InsufficientParts,
     NotEnoughMoney,
     InvalidParameter,
+    LocationClosed(Location),
+    LocationAlreadyOpen(Location),
+    OverCreditLimit,
 }
 
 #[derive(Debug, Clone)]
-pub(crate) struct Business {
+pub struct Business {
     name: String,
-    cash: f32,
-    scooters_working: u32,
-    scooters_broken: u32,
+    day: u32,
+    /// Every transaction so far; cash is whatever it adds up to.
+    ledger: Ledger,
+    /// The shop at each location, if one has been opened there.
+    shops: Locations<Option<Shop>>,
     scooter_parts: u32,
-    num_advertisements: u32,
+    bank: Bank,
+    fleet_config: FleetConfig,
+    location_config: Locations<LocationConfig>,
+    operating_config: OperatingConfig,
+    bank_config: BankConfig,
 }
 
 impl Business {
-    pub(crate) fn name(&self) -> String { self.name.clone() }
-    pub(crate) fn cash(&self) -> f32 { self.cash }
-    pub(crate) fn working_scooters(&self) -> u32 { self.scooters_working }
-    pub(crate) fn broken_scooters(&self) -> u32 { self.scooters_broken }
-    pub(crate) fn scooter_parts(&self) -> u32 { self.scooter_parts }
-    pub(crate) fn advertisements(&self) -> u32 { self.num_advertisements }
-
-    pub(crate) fn new(name: String) -> Self {
+    pub fn name(&self) -> String { self.name.clone() }
+    pub fn cash(&self) -> f32 { self.ledger.cash() }
+    pub fn ledger(&self) -> &Ledger { &self.ledger }
+    pub fn day(&self) -> u32 { self.day }
+    pub fn scooter_parts(&self) -> u32 { self.scooter_parts }
+    pub fn shop(&self, at: Location) -> Option<&Shop> { self.shops.get_ref(at).as_ref() }
+    pub fn shops_by_location(&self) -> &Locations<Option<Shop>> { &self.shops }
+    pub fn is_open(&self, at: Location) -> bool { self.shop(at).is_some() }
+
+    /// The open shops, in the order of `Location::ALL`.
+    pub fn shops(&self) -> impl Iterator<Item = (Location, &Shop)> {
+        Location::ALL.into_iter().filter_map(|at| self.shop(at).map(|shop| (at, shop)))
+    }
+
+    pub fn working_scooters(&self) -> u32 { self.shops().map(|(_, shop)| shop.fleet().working()).sum() }
+    pub fn broken_scooters(&self) -> u32 { self.shops().map(|(_, shop)| shop.fleet().broken()).sum() }
+    pub fn rentable_scooters(&self, at: Location) -> u32 {
+        self.shop(at).map(|shop| shop.fleet().rentable(&self.fleet_config)).unwrap_or(0)
+    }
+
+    /// Rent due at the end of each day for every open shop.
+    pub fn daily_rent(&self) -> f32 {
+        self.shops().map(|(at, _)| self.location_config.get(at).rent).sum()
+    }
+
+    /// Everything paid at the end of each day: rent, plus wages and storage for every shop.
+    pub fn daily_costs(&self) -> f32 {
+        self.shops().map(|(at, shop)| self.shop_daily_costs(at, shop)).sum()
+    }
+
+    /// Insurance due at the end of the season for the scooters owned now.
+    pub fn season_insurance(&self) -> f32 {
+        self.scooters_owned() as f32 * self.operating_config.insurance_per_scooter
+    }
+
+    /// Whether cash has gone below zero, into the overdraft or past it.
+    pub fn in_debt(&self) -> bool { self.cash() < 0.0 }
+
+    pub fn bank(&self) -> &Bank { &self.bank }
+
+    /// Cash, plus what the scooters would sell for at `resale_price` each
+    /// when new, less what's owed to the bank.
+    pub fn net_worth(&self, resale_price: f32) -> f32 {
+        let scooters: f32 = self.shops()
+            .flat_map(|(_, shop)| shop.fleet().scooters())
+            .map(|scooter| resale_price * (1.0 - scooter.wear()))
+            .sum();
+        self.cash() + scooters - self.bank.owed()
+    }
+
+    /// Whether cash has gone below what the overdraft allows.
+    pub fn past_overdraft(&self) -> bool { self.overdrawn() > self.bank_config.overdraft_limit }
+
+    /// The most the bank will lend in total, loans and overdraft together.
+    pub fn credit_limit(&self) -> f32 { self.bank.credit_limit(&self.ledger, self.day, &self.bank_config) }
+
+    /// How far cash is below zero.
+    pub fn overdrawn(&self) -> f32 { (-self.cash()).max(0.0) }
+
+    /// How much more the bank will lend on top of what's already owed.
+    pub fn credit_available(&self) -> f32 {
+        (self.credit_limit() - self.bank.owed() - self.overdrawn()).max(0.0)
+    }
+
+    /// How far below zero cash may go to pay for things. Loans use up credit the overdraft would need.
+    pub fn overdraft_limit(&self) -> f32 {
+        self.bank_config.overdraft_limit.min((self.credit_limit() - self.bank.owed()).max(0.0))
+    }
+
+    /// The most that can be spent right now, overdraft included.
+    pub fn spending_power(&self) -> f32 { self.cash() + self.overdraft_limit() }
+
+    fn scooters_owned(&self) -> u32 { self.working_scooters() + self.broken_scooters() }
+
+    fn shop_daily_costs(&self, at: Location, shop: &Shop) -> f32 {
+        let owned = shop.fleet().working() + shop.fleet().broken();
+        self.location_config.get(at).rent
+            + self.operating_config.wages_per_shop
+            + owned as f32 * self.operating_config.storage_per_scooter
+    }
+
+    pub fn new(name: String, config: &GameConfig) -> Self {
+        let mut shops = Locations::default();
+        *shops.get_mut(config.starting.location) = Some(Shop::new(Fleet::new(config.starting.scooters, 1)));
         Self {
             name,
-            cash: STARTING_CASH,
-            scooters_working: STARTING_SCOOTERS,
-            scooters_broken: 0,
+            day: 1,
+            ledger: Ledger::opening(1, config.starting.cash),
+            shops,
             scooter_parts: 0,
-            num_advertisements: 0,
+            bank: Bank::default(),
+            fleet_config: config.fleet,
+            location_config: config.locations,
+            operating_config: config.operating,
+            bank_config: config.bank,
         }
     }
 
-    pub(crate) fn with_cash(mut self, cash: f32) -> Self { self.cash = cash; self}
-    pub(crate) fn with_working_scooters(mut self, num: u32) -> Self { self.scooters_working = num; self }
-    pub(crate) fn with_broken_scooters(mut self, num: u32) -> Self { self.scooters_broken = num; self }
-    pub(crate) fn with_scooter_parts(mut self, num: u32) -> Self { self.scooter_parts = num; self }
-    pub(crate) fn with_num_adverts(mut self, num: u32) -> Self { self.num_advertisements = num; self }
+    /// Start the books over with just `cash` in them.
+    pub fn with_cash(mut self, cash: f32) -> Self { self.ledger = Ledger::opening(self.day, cash); self }
+    pub fn with_ledger(mut self, ledger: Ledger) -> Self { self.ledger = ledger; self }
+    pub fn with_day(mut self, day: u32) -> Self { self.day = day; self }
+    pub fn with_shops(mut self, shops: Locations<Option<Shop>>) -> Self { self.shops = shops; self }
+    pub fn with_shop(mut self, at: Location, shop: Shop) -> Self { *self.shops.get_mut(at) = Some(shop); self }
+    pub fn with_scooter_parts(mut self, num: u32) -> Self { self.scooter_parts = num; self }
+    pub fn with_bank(mut self, bank: Bank) -> Self { self.bank = bank; self }
+
+    fn shop_mut(&mut self, at: Location) -> Result<&mut Shop, ManagementError> {
+        self.shops.get_mut(at).as_mut().ok_or(ManagementError::LocationClosed(at))
+    }
+
+    /// Open a shop at `at`, paying the location's opening cost. It starts with no scooters.
+    pub fn open_location(&mut self, at: Location) -> DidItWork {
+        if self.is_open(at) { return Err(ManagementError::LocationAlreadyOpen(at)) }
+        let cost = self.location_config.get(at).open_cost;
+        if cost > self.spending_power() { return Err(ManagementError::NotEnoughMoney) }
+        let mut fleet = Fleet::default();
+        fleet.skip_ids_to(self.next_scooter_id());
+        *self.shops.get_mut(at) = Some(Shop::new(fleet));
+        self.ledger.record(self.day, Category::ShopOpenings, -cost);
+        Ok(())
+    }
+
+    /// Scooter ids run across every shop, so no two scooters share one.
+    fn next_scooter_id(&self) -> u32 {
+        self.shops().map(|(_, shop)| shop.fleet().next_id()).max().unwrap_or(0)
+    }
 
-    pub(crate) fn buy_scooters(&mut self, num: u32, cost_per: f32) -> DidItWork {
+    pub fn buy_scooters(&mut self, at: Location, num: u32, cost_per: f32) -> DidItWork {
         if cost_per < 0_f32 { return  Err(ManagementError::InvalidParameter) }
+        let next_id = self.next_scooter_id();
+        let day = self.day;
         let cost = num as f32 * cost_per;
-        if cost > self.cash { return Err(ManagementError::NotEnoughMoney) }
-        self.scooters_working += num;
-        self.cash -= cost;
+        if cost > self.spending_power() { return Err(ManagementError::NotEnoughMoney) }
+        let fleet = self.shop_mut(at)?.fleet_mut();
+        fleet.skip_ids_to(next_id);
+        fleet.add(num, day);
+        self.ledger.record(day, Category::ScooterPurchases, -cost);
         Ok(())
     }
 
-    pub(crate) fn sell_working_scooters(&mut self, num: u32, cost_per: f32) -> DidItWork {
+    /// Sells the most worn working scooters at `at` first. `cost_per` is the
+    /// price of a scooter in new condition; worn scooters go for proportionally
+    /// less. Returns the total income from the sale.
+    pub fn sell_working_scooters(&mut self, at: Location, num: u32, cost_per: f32) -> Result<f32, ManagementError> {
         if cost_per < 0_f32  { return  Err(ManagementError::InvalidParameter) }
-        if num > self.scooters_working { return Err(ManagementError::InsufficientWorkingScooters) }
-        let cost = num as f32 * cost_per;
-        self.scooters_working -= num;
-        self.cash += cost;
-        Ok(())
+        let fleet = self.shop_mut(at)?.fleet_mut();
+        if num > fleet.working() { return Err(ManagementError::InsufficientWorkingScooters) }
+        let income: f32 = fleet.remove_working(num).iter()
+            .map(|scooter| cost_per * (1.0 - scooter.wear()))
+            .sum();
+        self.ledger.record(self.day, Category::ScooterSales, income);
+        Ok(income)
     }
 
-    // TODO: Create tests
-    pub(crate) fn repair_scooters(&mut self, num: u32) -> DidItWork {
-        if num > self.scooter_parts { return Err(ManagementError::InsufficientParts) }
-        if num > self.scooters_broken { return Err(ManagementError::InsufficientBrokenScooters) }
-        self.scooters_broken -= num;
-        self.scooters_working += num;
+    pub fn repair_scooters(&mut self, at: Location, num: u32) -> DidItWork {
+        let parts = self.scooter_parts;
+        let config = self.fleet_config;
+        let fleet = self.shop_mut(at)?.fleet_mut();
+        if num > parts { return Err(ManagementError::InsufficientParts) }
+        if num > fleet.broken() { return Err(ManagementError::InsufficientBrokenScooters) }
+        fleet.repair(num, &config);
         self.scooter_parts -= num;
         Ok(())
     }
 
-    // TODO: Create tests
-    pub(crate) fn buy_scooter_parts(&mut self, num: u32, cost_per: f32) -> DidItWork {
+    /// Move `num` working scooters, most worn first, from one shop to another
+    /// at `cost_per` each. They can be rented from their new shop straight away.
+    pub fn transfer_scooters(&mut self, from: Location, to: Location, num: u32, cost_per: f32) -> DidItWork {
+        if cost_per < 0_f32 || from == to { return Err(ManagementError::InvalidParameter) }
+        self.shop_mut(to)?;
+        let cost = num as f32 * cost_per;
+        if cost > self.spending_power() { return Err(ManagementError::NotEnoughMoney) }
+        let fleet = self.shop_mut(from)?.fleet_mut();
+        if num > fleet.working() { return Err(ManagementError::InsufficientWorkingScooters) }
+        let moving = fleet.remove_working(num);
+        let destination = self.shop_mut(to)?.fleet_mut();
+        moving.into_iter().for_each(|scooter| destination.push(scooter));
+        self.ledger.record(self.day, Category::Transfers, -cost);
+        Ok(())
+    }
+
+    /// Borrow `amount` from the bank, to be paid back over its loan term.
+    pub fn take_loan(&mut self, amount: f32) -> Result<Loan, ManagementError> {
+        if !amount.is_finite() || amount <= 0.0 { return Err(ManagementError::InvalidParameter) }
+        if amount > self.credit_available() { return Err(ManagementError::OverCreditLimit) }
+        let loan = self.bank.borrow(self.day, amount, &self.bank_config);
+        self.ledger.record(self.day, Category::Borrowing, amount);
+        Ok(loan)
+    }
+
+    /// Pay up to `amount` off the loans early, oldest first, returning how much was paid.
+    /// Repayments come out of cash, not the overdraft.
+    pub fn repay_loans(&mut self, amount: f32) -> Result<f32, ManagementError> {
+        if !amount.is_finite() || amount < 0.0 { return Err(ManagementError::InvalidParameter) }
+        let amount = amount.min(self.bank.owed());
+        if amount > self.cash() { return Err(ManagementError::NotEnoughMoney) }
+        let paid = self.bank.repay(amount);
+        self.ledger.record(self.day, Category::LoanRepayments, -paid);
+        Ok(paid)
+    }
+
+    pub fn buy_scooter_parts(&mut self, num: u32, cost_per: f32) -> DidItWork {
         if cost_per < 0_f32 { return Err(ManagementError::InvalidParameter) }
         let cost = num as f32 * cost_per;
-        if cost > self.cash { return Err(ManagementError::NotEnoughMoney) }
-        self.cash -= cost;
+        if cost > self.spending_power() { return Err(ManagementError::NotEnoughMoney) }
+        self.ledger.record(self.day, Category::Parts, -cost);
         self.scooter_parts += num;
         Ok(())
     }
 
-    pub(crate) fn new_day(&mut self) {
-        self.num_advertisements = 0;
+    /// Rolls over to the next day: adverts run out, scooters charge overnight
+    /// and every open shop pays its rent, wages and storage. When the day was
+    /// the last of its season, insurance on every scooter is paid too. Then the
+    /// day's loan payments go out, and if cash has ended up below zero the bank
+    /// charges for the overdraft. All of it is paid even if there isn't the
+    /// cash, leaving the business in debt.
+    pub fn new_day(&mut self, season_ended: bool) {
+        let day = self.day;
+        let operating = self.operating_config;
+        for at in Location::ALL {
+            if let Some(shop) = self.shops.get_ref(at) {
+                let owned = shop.fleet().working() + shop.fleet().broken();
+                self.ledger.record(day, Category::Rent, -self.location_config.get(at).rent);
+                self.ledger.record(day, Category::Wages, -operating.wages_per_shop);
+                self.ledger.record(day, Category::Storage, -(owned as f32 * operating.storage_per_scooter));
+            }
+        }
+        if season_ended {
+            self.ledger.record(day, Category::Insurance, -self.season_insurance());
+        }
+        let (interest, principal) = self.bank.pay_day();
+        if interest > 0.0 { self.ledger.record(day, Category::Interest, -interest) }
+        if principal > 0.0 { self.ledger.record(day, Category::LoanRepayments, -principal) }
+        if self.in_debt() {
+            let fee = self.bank_config.overdraft_fee + self.overdrawn() * self.bank_config.overdraft_daily_rate;
+            self.ledger.record(day, Category::OverdraftFees, -fee);
+        }
+        let config = self.fleet_config;
+        for at in Location::ALL {
+            if let Some(shop) = self.shops.get_mut(at) {
+                shop.set_advertisements(0);
+                shop.fleet_mut().recharge(&config);
+            }
+        }
+        self.day += 1;
     }
 
     ///
     ///
     /// # Arguments
     ///
-    /// * num: Number of scooters to rent. This must not exceed available working scooters.
-    /// * cost_per: Amount to gain per scooter rental.
-    /// * temperature: The current temperature
-    /// * weather: the current weather type
+    /// * rng: Source of randomness for demand and breakage.
+    /// * demand: The model deciding how many customers turn up at this price.
+    /// * at: The shop renting the scooters out.
+    /// * num: Number of scooters to put out for rent. This must not exceed the shop's charged, working scooters.
+    /// * prices: Amount to gain per scooter rental in each period of the day.
+    /// * weather: Today's weather, temperature, readings and season
     ///
     /// returns: Result<Receipt, ManagementError>
     ///
-    /// If result is Ok, returns a Receipt object from this rental period.
-    /// Temperature and weather are used to determine what percentage of scooters are
-    /// actually rented for the day.
+    /// If result is Ok, returns a Receipt object for the shop's whole day, with
+    /// the results of each period inside it.
+    /// The same scooters stay out all day. In each period the demand model says
+    /// how many customers from each segment want a scooter in that period's
+    /// weather; luck moves that by up to 10% either way, and when there are more
+    /// customers than deployed scooters still charged and working, every segment
+    /// loses the same share. Each rented scooter picks up mileage and wear, and
+    /// may break down; scooters held back are left alone.
     ///
     /// # Examples
     ///
-    /// ```
-    /// rent_scooters(10, 20.0, Temperature::Cold, WeatherType::Stormy) -> Ok(Receipt{profit: 10.0, broken_scooters: 1})
+    /// ```text
+    /// rent_scooters(&mut rng, &demand, Location::Downtown, 10, Periods::all(20.0), &cold_stormy) -> Ok(Receipt{deployed: 10, rented: 1, profit: 20.0, broken_scooters: 1, ..})
     ///
-    /// rent_scooters(10, -10.0, Temperature::Cold, WeatherType::Stormy) -> Err(ManagementError::InvalidParameter)
+    /// rent_scooters(&mut rng, &demand, Location::Downtown, 10, Periods::all(-10.0), &cold_stormy) -> Err(ManagementError::InvalidParameter)
+    ///
+    /// rent_scooters(&mut rng, &demand, Location::Downtown, 500, Periods::all(20.0), &cold_stormy) -> Err(ManagementError::InsufficientWorkingScooters)
+    ///
+    /// rent_scooters(&mut rng, &demand, Location::Beach, 10, Periods::all(20.0), &cold_stormy) -> Err(ManagementError::LocationClosed(Location::Beach))
     /// ```
-    // TODO: Create tests
-    pub(crate) fn rent_scooters(
+    pub fn rent_scooters(
         &mut self,
-        cost_per: f32,
-        temperature: Temperature,
-        weather: WeatherType,
+        rng: &mut impl Rng,
+        demand: &dyn DemandModel,
+        at: Location,
+        num: u32,
+        prices: Periods<f32>,
+        weather: &Weather,
     ) -> Result<Receipt, ManagementError> {
-        let num = self.scooters_working;
-        if cost_per < 0_f32 { return Err(ManagementError::InvalidParameter) }
-        if num > self.scooters_working { return Err(ManagementError::InsufficientWorkingScooters) }
-        // Determine actual number of rented scooters
-        let combined_mod = &self.combined_modifier(temperature, weather, cost_per);
-        let price_mod = OPTIMAL_RENTAL_PRICE / cost_per;
-        let rented: u32 = max(0, min((num as f32 * combined_mod * price_mod).floor() as u32, num));
-        // Do the transaction
-        let profit = rented as f32 * cost_per;
-        self.cash += profit;
-        // Breaking scooters
-        let mut broken_scooters = 0_u32;
-        for _ in 0..rented {
-            if random::<f32>() < CHANCE_SCOOTER_BREAKS {
-                broken_scooters += 1;
-            }
+        if prices.iter().any(|(_, price)| !price.is_finite() || price < 0_f32) {
+            return Err(ManagementError::InvalidParameter)
         }
-        self.scooters_working -= broken_scooters;
-        self.scooters_broken += broken_scooters;
-        Ok(Receipt::new(profit, broken_scooters))
-    }
-
-    // TODO: Create tests
-    // TODO: Add this to rent_scooters
-    fn combined_modifier(
-        &self,
-        temperature: Temperature,
-        current_weather: WeatherType,
-        cost_per: f32,
-    ) -> f32 {
-        let advert_effect = ADVERT_EFFECT * min(self.num_advertisements, self.scooters_working) as f32;
-        let cost_effect = f32::max(-1.0, f32::min(1.0,(OPTIMAL_RENTAL_PRICE - cost_per) / 10.0));
-        let mut val = 1.0 + advert_effect + cost_effect;
-        let rnd = rand::thread_rng().gen_range(-0.1 .. 0.1);
-        match (temperature, current_weather) {
-            (Temperature::Scorching, WeatherType::Cloudy) => val *= 0.25,
-            (Temperature::Scorching, WeatherType::Sunny) => val *= 0.05,
-            (Temperature::Scorching, WeatherType::Rainy) => val *= 0.10,
-            (Temperature::Scorching, WeatherType::Stormy) => val *= 0.00,
-            (Temperature::Hot, WeatherType::Cloudy) => val *= 0.60,
-            (Temperature::Hot, WeatherType::Sunny) => val *= 0.50,
-            (Temperature::Hot, WeatherType::Rainy) => val *= 0.10,
-            (Temperature::Hot, WeatherType::Stormy) => val *= 0.05,
-            (Temperature::Warm, WeatherType::Cloudy) => val *= 0.90,
-            (Temperature::Warm, WeatherType::Sunny) => val *= 1.00,
-            (Temperature::Warm, WeatherType::Rainy) => val *= 0.20,
-            (Temperature::Warm, WeatherType::Stormy) => val *= 0.05,
-            (Temperature::Cool, WeatherType::Cloudy) => val *= 0.90,
-            (Temperature::Cool, WeatherType::Sunny) => val *= 1.00,
-            (Temperature::Cool, WeatherType::Rainy) => val *= 0.20,
-            (Temperature::Cool, WeatherType::Stormy) => val *= 0.05,
-            (Temperature::Cold, WeatherType::Cloudy) => val *= 0.50,
-            (Temperature::Cold, WeatherType::Sunny) => val *= 0.60,
-            (Temperature::Cold, WeatherType::Rainy) => val *= 0.10,
-            (Temperature::Cold, WeatherType::Stormy) => val *= 0.05,
-            (Temperature::Freezing, WeatherType::Cloudy) => val *= 0.10,
-            (Temperature::Freezing, WeatherType::Sunny) => val *= 0.25,
-            (Temperature::Freezing, WeatherType::Rainy) => val *= 0.05,
-            (Temperature::Freezing, WeatherType::Stormy) => val *= 0.00,
-            (t,w) => {
-                panic!("The temp ({:?}) and weather ({:?}) was not accounted for.",t,w)
-            },
+        let config = self.fleet_config;
+        let shop = self.shops.get_mut(at).as_mut().ok_or(ManagementError::LocationClosed(at))?;
+        if num > shop.fleet().rentable(&config) { return Err(ManagementError::InsufficientWorkingScooters) }
+        let advertisements = shop.advertisements();
+        let fleet = shop.fleet_mut();
+        let deployed = fleet.deploy(num, &config);
+        let mut receipt = Receipt::new(num, 0, 0.0, 0).with_location(at);
+        for (period, cost_per) in prices.iter() {
+            // Determine actual number of rented scooters
+            let context = DemandContext {
+                location: at,
+                period,
+                temperature: weather.get_temperature(),
+                weather: weather.get_period(period),
+                conditions: weather.get_conditions(),
+                season: weather.get_season(),
+                advertisements,
+                deployed: num,
+            };
+            let available = fleet.rentable_of(&deployed, &config);
+            let luck = 1.0 + rng.gen_range(-0.1 .. 0.1);
+            let segments = demand.expected_customers(&context, cost_per)
+                .map(|_, customers| customers * luck)
+                .share_out(available);
+            let rented = segments.total();
+            // Do the transaction
+            let profit = rented as f32 * cost_per;
+            // Wear and breaking scooters
+            let broken_scooters = fleet.ride(&deployed, rented, &context.conditions, rng, &config);
+            receipt.add_period(PeriodReceipt {
+                period,
+                cost_per,
+                weather: context.weather,
+                rented,
+                profit,
+                broken_scooters,
+                segments,
+            });
         }
-        f32::max(0.0_f32, f32::min(1.0_f32, val + rnd))
+        self.ledger.record(self.day, Category::Rentals, receipt.profit());
+        Ok(receipt)
     }
 
-    pub(crate) fn buy_advertisements(&mut self, num: u32, cost: f32) -> DidItWork {
-        if num as f32 * cost > self.cash { return Err(ManagementError::NotEnoughMoney) }
-        self.num_advertisements = num;
-        self.cash -= num as f32 * cost;
+    pub fn buy_advertisements(&mut self, at: Location, num: u32, cost: f32) -> DidItWork {
+        let cash = self.spending_power();
+        let day = self.day;
+        let shop = self.shop_mut(at)?;
+        if num as f32 * cost > cash { return Err(ManagementError::NotEnoughMoney) }
+        shop.set_advertisements(num);
+        self.ledger.record(day, Category::Advertising, -(num as f32 * cost));
         Ok(())
     }
 }
 
-// TODO: Create tests
 // Do I really need this? Consider replacing with DidItWork
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Receipt {
+    location: Location,
+    deployed: u32,
+    rented: u32,
     profit: f32,
     broken_scooters: u32,
+    /// Who the scooters were rented to.
+    segments: Segments<u32>,
+    /// How each period of the day went, in order.
+    periods: Vec<PeriodReceipt>,
 }
 
 impl Receipt {
-    pub(crate) fn new(profit: f32, broken_scooters: u32) -> Self {
-        Self { profit, broken_scooters }
+    pub fn new(deployed: u32, rented: u32, profit: f32, broken_scooters: u32) -> Self {
+        Self {
+            location: Location::Downtown,
+            deployed,
+            rented,
+            profit,
+            broken_scooters,
+            segments: Segments::default(),
+            periods: Vec::new(),
+        }
     }
 
+    pub fn with_location(mut self, location: Location) -> Self
+    { self.location = location; self }
+    pub fn with_periods(mut self, periods: Vec<PeriodReceipt>) -> Self
+    { self.periods = periods; self }
+
+    pub fn with_segments(mut self, segments: Segments<u32>) -> Self
+    { self.segments = segments; self }
+
+    /// Add one period's results to the day's totals.
+    pub fn add_period(&mut self, period: PeriodReceipt) {
+        self.rented += period.rented;
+        self.profit += period.profit;
+        self.broken_scooters += period.broken_scooters;
+        self.segments = self.segments.map(|segment, rented| rented + period.segments.get(segment));
+        self.periods.push(period);
+    }
+
+    pub fn location(&self) -> Location { self.location }
+    pub fn deployed(&self) -> u32 { self.deployed }
+    pub fn rented(&self) -> u32 { self.rented }
     pub fn profit(&self) -> f32 { self.profit }
     pub fn broken_scooters(&self) -> u32 { self.broken_scooters }
+    pub fn segments(&self) -> Segments<u32> { self.segments }
+    pub fn periods(&self) -> &[PeriodReceipt] { &self.periods }
+}
+
+/// What happened during one period of a day's rentals.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct PeriodReceipt {
+    pub period: Period,
+    pub cost_per: f32,
+    pub weather: WeatherType,
+    pub rented: u32,
+    pub profit: f32,
+    pub broken_scooters: u32,
+    pub segments: Segments<u32>,
 }
 
 #[cfg(test)]
 mod buy_scooters_tests {
     use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::ledger::Category;
+    use crate::location::Location;
 
     #[test]
     fn buy_some_scooters() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        if business.buy_scooters(2, 40_f32).is_ok() {
-            assert_eq!(business.cash(), 20_f32)
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        if business.buy_scooters(Location::Downtown, 2, 40_f32).is_ok() {
+            assert_eq!(business.cash(), 20_f32);
+            assert_eq!(business.ledger().total(Category::ScooterPurchases), -80_f32);
         } else { panic!("Buy was not ok") }
     }
+    #[test]
+    fn cashflow_problems() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.buy_scooters(Location::Downtown, 10, 40_f32), Err(ManagementError::NotEnoughMoney))
+    }
+    #[test]
+    fn negative_cost() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.buy_scooters(Location::Downtown, 10, -40_f32), Err(ManagementError::InvalidParameter))
+    }
+}
+
+#[cfg(test)]
+mod buy_scooter_parts_tests {
+    use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::ledger::Category;
+
+    #[test]
+    fn buy_some_parts() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.buy_scooter_parts(3, 10_f32), Ok(()));
+        assert_eq!(business.scooter_parts(), 3);
+        assert_eq!(business.cash(), 70_f32);
+        assert_eq!(business.ledger().total(Category::Parts), -30_f32);
+    }
+    #[test]
     fn cashflow_problems() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        assert_eq!(business.buy_scooters(10, 40_f32), Err(ManagementError::NotEnoughMoney))
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.buy_scooter_parts(50, 10_f32), Err(ManagementError::NotEnoughMoney));
+        assert_eq!(business.scooter_parts(), 0);
     }
+    #[test]
     fn negative_cost() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        assert_eq!(business.buy_scooters(10, -40_f32), Err(ManagementError::InvalidParameter))
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.buy_scooter_parts(1, -10_f32), Err(ManagementError::InvalidParameter))
+    }
+}
+
+#[cfg(test)]
+mod receipt_tests {
+    use crate::business::{PeriodReceipt, Receipt};
+    use crate::demand::Segments;
+    use crate::location::Location;
+    use crate::period::Period;
+    use crate::weather::WeatherType;
+
+    fn period(period: Period, rented: u32, broken_scooters: u32, commuters: u32) -> PeriodReceipt {
+        let segments = Segments { commuters, tourists: rented - commuters, students: 0, leisure: 0 };
+        PeriodReceipt {
+            period, cost_per: 10.0, weather: WeatherType::Sunny, rented, profit: rented as f32 * 10.0,
+            broken_scooters, segments,
+        }
+    }
+
+    #[test]
+    fn periods_add_up_to_the_day() {
+        let mut receipt = Receipt::new(6, 0, 0.0, 0).with_location(Location::Beach);
+        receipt.add_period(period(Period::Morning, 4, 1, 3));
+        receipt.add_period(period(Period::Evening, 2, 0, 0));
+        assert_eq!(receipt.location(), Location::Beach);
+        assert_eq!(receipt.deployed(), 6);
+        assert_eq!(receipt.rented(), 6);
+        assert_eq!(receipt.profit(), 60.0);
+        assert_eq!(receipt.broken_scooters(), 1);
+        assert_eq!(receipt.segments().commuters, 3);
+        assert_eq!(receipt.segments().tourists, 3);
+        assert_eq!(receipt.periods().len(), 2);
+        assert_eq!(receipt.periods()[1].period, Period::Evening);
     }
 }
 
@@ -236,39 +543,337 @@ mod buy_scooters_tests {
 #[cfg(test)]
 mod sell_scooters_tests {
     use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::location::Location;
 
     #[test]
     fn sell_working_scooters() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        if business.sell_working_scooters(1, 10_f32).is_ok() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        if business.sell_working_scooters(Location::Downtown, 1, 10_f32).is_ok() {
             assert_eq!(business.cash(), 110_f32)
         } else { panic!("Sell was not ok") }
 
     }
     #[test]
     fn not_enough_to_sell() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        assert_eq!(business.sell_working_scooters(20, 40_f32), Err(ManagementError::InsufficientWorkingScooters))
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.sell_working_scooters(Location::Downtown, 20, 40_f32), Err(ManagementError::InsufficientWorkingScooters))
     }
     #[test]
     fn negative_cost() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        assert_eq!(business.sell_working_scooters(10, -40_f32), Err(ManagementError::InvalidParameter))
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(business.sell_working_scooters(Location::Downtown, 10, -40_f32), Err(ManagementError::InvalidParameter))
+    }
+}
+
+#[cfg(test)]
+mod repair_scooters_tests {
+    use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::fleet::{Fleet, Scooter, ScooterStatus};
+    use crate::location::{Location, Shop};
+
+    #[test]
+    fn repair_with_parts() {
+        let mut fleet = Fleet::new(1, 1);
+        fleet.push(Scooter::new(1, 1).with_wear(0.6).with_status(ScooterStatus::Broken));
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default()).with_shop(Location::Downtown, Shop::new(fleet)).with_scooter_parts(1);
+        assert_eq!(business.repair_scooters(Location::Downtown, 1), Ok(()));
+        assert_eq!(business.working_scooters(), 2);
+        assert_eq!(business.scooter_parts(), 0);
+    }
+    #[test]
+    fn no_parts() {
+        let mut fleet = Fleet::new(1, 1);
+        fleet.push(Scooter::new(1, 1).with_status(ScooterStatus::Broken));
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default()).with_shop(Location::Downtown, Shop::new(fleet));
+        assert_eq!(business.repair_scooters(Location::Downtown, 1), Err(ManagementError::InsufficientParts))
     }
 }
 
 #[cfg(test)]
 mod rent_scooters_test {
-    use crate::business::Business;
-    use crate::weather::Weather;
+    use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::demand::ElasticDemand;
+    use crate::location::Location;
+    use crate::period::Periods;
+    use crate::rng::seeded;
+    use crate::weather::{Temperature, Weather, WeatherType};
+
+    fn demand() -> ElasticDemand {
+        ElasticDemand::new(GameConfig::default().demand)
+    }
+
+    fn warm_sunny() -> Weather {
+        Weather::default().with_temperature(Temperature::Warm).with_current_weather(WeatherType::Sunny)
+    }
 
     #[test]
     fn normal_rental() {
-        let mut business = Business::new("New Scoots, Inc.".to_string());
-        let mut weather = Weather::new();
-        weather.new_day();
-        let temp = weather.get_temperature();
-        let wt = weather.get_current();
-        dbg!(business.rent_scooters(15.0_f32, temp, wt).unwrap());
-    }
-}
\ No newline at end of file
+        let mut rng = seeded(1);
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        let mut weather = Weather::default();
+        weather.new_day(&mut rng);
+        dbg!(business.rent_scooters(&mut rng, &demand(), Location::Downtown, 10, Periods::all(15.0_f32), &weather).unwrap());
+    }
+
+    #[test]
+    fn held_back_scooters_are_untouched() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        let receipt = business.rent_scooters(&mut seeded(8), &demand(), Location::Downtown, 4, Periods::all(10.0), &warm_sunny()).unwrap();
+        assert_eq!(receipt.deployed(), 4);
+        assert!(receipt.periods().iter().all(|period| period.rented <= 4));
+        let untouched = business.shop(Location::Downtown).unwrap().fleet().scooters().iter().filter(|s| s.mileage() == 0.0).count();
+        assert!(untouched >= 6);
+    }
+
+    #[test]
+    fn cannot_deploy_more_than_rentable() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(
+            business.rent_scooters(&mut seeded(8), &demand(), Location::Downtown, 11, Periods::all(10.0), &warm_sunny()),
+            Err(ManagementError::InsufficientWorkingScooters)
+        );
+    }
+
+    #[test]
+    fn same_seed_same_receipt() {
+        let mut first = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        let mut second = first.clone();
+        let a = first.rent_scooters(&mut seeded(42), &demand(), Location::Downtown, 10, Periods::all(12.0), &warm_sunny());
+        let b = second.rent_scooters(&mut seeded(42), &demand(), Location::Downtown, 10, Periods::all(12.0), &warm_sunny());
+        assert_eq!(a, b);
+        assert_eq!(first.cash(), second.cash());
+    }
+
+    #[test]
+    fn free_rentals_do_not_divide_by_zero() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        let receipt = business.rent_scooters(&mut seeded(8), &demand(), Location::Downtown, 10, Periods::all(0.0), &warm_sunny()).unwrap();
+        assert!(receipt.rented() >= 10);
+        assert_eq!(receipt.profit(), 0.0);
+    }
+
+    #[test]
+    fn nonsense_prices_are_rejected() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        for price in [-10.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                business.rent_scooters(&mut seeded(8), &demand(), Location::Downtown, 10, Periods::all(price), &warm_sunny()),
+                Err(ManagementError::InvalidParameter)
+            );
+        }
+    }
+
+    #[test]
+    fn rentals_add_up_across_segments() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        let receipt = business.rent_scooters(&mut seeded(3), &demand(), Location::Downtown, 10, Periods::all(12.0), &warm_sunny()).unwrap();
+        assert!(receipt.rented() > 0);
+        assert_eq!(receipt.segments().total(), receipt.rented());
+    }
+
+    #[test]
+    fn each_period_has_its_own_price_and_weather() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        let weather = warm_sunny().with_period_weather(Periods {
+            morning: WeatherType::Sunny,
+            midday: WeatherType::Sunny,
+            evening: WeatherType::Stormy,
+            night: WeatherType::Stormy,
+        });
+        let prices = Periods { morning: 10.0, midday: 12.0, evening: 10.0, night: 1000.0 };
+        let receipt = business.rent_scooters(&mut seeded(3), &demand(), Location::Downtown, 10, prices, &weather).unwrap();
+        let periods = receipt.periods();
+        assert_eq!(periods.len(), 4);
+        assert_eq!(periods[1].cost_per, 12.0);
+        assert_eq!(periods[2].weather, WeatherType::Stormy);
+        assert!(periods[2].rented < periods[0].rented);
+        assert_eq!(periods[3].rented, 0);
+        assert_eq!(periods.iter().map(|period| period.profit).sum::<f32>(), receipt.profit());
+    }
+
+    #[test]
+    fn closed_shops_cannot_rent() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        assert_eq!(
+            business.rent_scooters(&mut seeded(8), &demand(), Location::Beach, 0, Periods::all(10.0), &warm_sunny()),
+            Err(ManagementError::LocationClosed(Location::Beach))
+        );
+    }
+}
+
+#[cfg(test)]
+mod location_tests {
+    use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::ledger::Category;
+    use crate::location::Location;
+
+    fn business() -> Business {
+        Business::new("New Scoots, Inc.".to_string(), &GameConfig::default()).with_cash(1000.0)
+    }
+
+    #[test]
+    fn open_a_new_shop() {
+        let mut business = business();
+        assert_eq!(business.open_location(Location::Beach), Ok(()));
+        assert!(business.is_open(Location::Beach));
+        assert_eq!(business.cash(), 700.0);
+        assert_eq!(business.shop(Location::Beach).unwrap().fleet().working(), 0);
+        assert_eq!(business.open_location(Location::Beach), Err(ManagementError::LocationAlreadyOpen(Location::Beach)));
+    }
+
+    #[test]
+    fn cannot_afford_to_open() {
+        let mut business = business().with_cash(50.0);
+        assert_eq!(business.open_location(Location::Campus), Err(ManagementError::NotEnoughMoney));
+        assert!(!business.is_open(Location::Campus));
+    }
+
+    #[test]
+    fn scooter_ids_are_unique_across_shops() {
+        let mut business = business();
+        business.open_location(Location::Campus).unwrap();
+        business.buy_scooters(Location::Campus, 3, 10.0).unwrap();
+        business.buy_scooters(Location::Downtown, 2, 10.0).unwrap();
+        let mut ids: Vec<u32> = business.shops()
+            .flat_map(|(_, shop)| shop.fleet().scooters().iter().map(|scooter| scooter.id()).collect::<Vec<_>>())
+            .collect();
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+    }
+
+    #[test]
+    fn transfer_between_shops() {
+        let mut business = business();
+        business.open_location(Location::Beach).unwrap();
+        assert_eq!(business.transfer_scooters(Location::Downtown, Location::Beach, 4, 5.0), Ok(()));
+        assert_eq!(business.shop(Location::Downtown).unwrap().fleet().working(), 6);
+        assert_eq!(business.shop(Location::Beach).unwrap().fleet().working(), 4);
+        assert_eq!(business.working_scooters(), 10);
+        assert_eq!(business.cash(), 680.0);
+    }
+
+    #[test]
+    fn cannot_transfer_to_a_closed_shop() {
+        let mut business = business();
+        assert_eq!(
+            business.transfer_scooters(Location::Downtown, Location::Campus, 1, 5.0),
+            Err(ManagementError::LocationClosed(Location::Campus))
+        );
+        assert_eq!(business.working_scooters(), 10);
+    }
+
+    #[test]
+    fn rent_is_paid_for_every_open_shop() {
+        let mut business = business();
+        business.open_location(Location::Campus).unwrap();
+        business.new_day(false);
+        assert_eq!(business.ledger().total(Category::Rent), -15.0);
+        assert_eq!(business.ledger().on_day(1).filter(|entry| entry.category == Category::Rent).count(), 2);
+    }
+}
+
+#[cfg(test)]
+mod operating_costs_tests {
+    use crate::business::Business;
+    use crate::config::GameConfig;
+    use crate::ledger::Category;
+
+    #[test]
+    fn daily_costs_are_paid_every_night() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        // rent 10, wages 10 and storage for 10 scooters at 0.5
+        assert_eq!(business.daily_costs(), 25.0);
+        business.new_day(false);
+        assert_eq!(business.cash(), 75.0);
+        assert_eq!(business.ledger().total(Category::Storage), -5.0);
+        assert_eq!(business.ledger().total(Category::Insurance), 0.0);
+    }
+
+    #[test]
+    fn insurance_is_paid_when_the_season_ends() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default());
+        business.new_day(true);
+        assert_eq!(business.ledger().total(Category::Insurance), -30.0);
+        assert_eq!(business.cash(), 45.0);
+    }
+
+    #[test]
+    fn costs_are_paid_into_debt() {
+        let mut business = Business::new("New Scoots, Inc.".to_string(), &GameConfig::default()).with_cash(10.0);
+        business.new_day(false);
+        assert!(business.in_debt());
+        // the overdraft charges for the night on top of the $15 shortfall
+        let fee = 2.0 + 15.0 * 0.01;
+        assert_eq!(business.ledger().total(Category::OverdraftFees), -fee);
+        assert_eq!(business.cash(), -15.0 - fee);
+    }
+}
+
+#[cfg(test)]
+mod bank_tests {
+    use crate::business::{Business, ManagementError};
+    use crate::config::GameConfig;
+    use crate::ledger::{Account, Category};
+    use crate::location::Location;
+
+    fn business() -> Business {
+        Business::new("New Scoots, Inc.".to_string(), &GameConfig::default())
+    }
+
+    #[test]
+    fn borrow_to_buy_scooters() {
+        let mut business = business();
+        assert_eq!(business.buy_scooters(Location::Downtown, 3, 100.0), Err(ManagementError::NotEnoughMoney));
+        business.take_loan(150.0).unwrap();
+        assert_eq!(business.cash(), 250.0);
+        assert_eq!(business.bank().owed(), 150.0);
+        assert_eq!(business.buy_scooters(Location::Downtown, 3, 100.0), Ok(()));
+    }
+
+    #[test]
+    fn the_overdraft_covers_a_shortfall() {
+        let mut business = business();
+        assert_eq!(business.spending_power(), 200.0);
+        assert_eq!(business.buy_scooters(Location::Downtown, 2, 90.0), Ok(()));
+        assert_eq!(business.cash(), -80.0);
+        assert_eq!(business.buy_scooter_parts(1, 25.0), Err(ManagementError::NotEnoughMoney));
+    }
+
+    #[test]
+    fn credit_runs_out() {
+        let mut business = business();
+        assert_eq!(business.credit_limit(), 200.0);
+        business.take_loan(150.0).unwrap();
+        assert_eq!(business.take_loan(60.0), Err(ManagementError::OverCreditLimit));
+        // only what's left of the credit can be overdrawn
+        assert_eq!(business.overdraft_limit(), 50.0);
+        assert_eq!(business.take_loan(-5.0), Err(ManagementError::InvalidParameter));
+    }
+
+    #[test]
+    fn loans_are_paid_back_each_night() {
+        let mut business = business().with_cash(1000.0);
+        let loan = business.take_loan(100.0).unwrap();
+        business.new_day(false);
+        assert!(business.bank().owed() < 100.0);
+        let interest = business.ledger().total(Category::Interest);
+        let repaid = business.ledger().total(Category::LoanRepayments);
+        assert!((interest + repaid + loan.daily_payment).abs() < 0.001);
+    }
+
+    #[test]
+    fn repay_early() {
+        let mut business = business();
+        business.take_loan(50.0).unwrap();
+        assert_eq!(business.repay_loans(80.0), Ok(50.0));
+        assert_eq!(business.cash(), 100.0);
+        assert!(business.bank().loans().is_empty());
+        assert_eq!(business.ledger().balance(Account::Loans), 0.0);
+    }
+}
@@ -22,19 +22,6 @@ pub fn get_input_u32() -> Result<u32, ProcessingError> {
     }
 }
 
-pub fn get_input_f32() -> Result<f32, ProcessingError> {
-    let mut input = String::new();
-    match io::stdin().read_line(&mut input) {
-        Ok(_) => {
-            match input.trim().parse::<f32>() {
-                Ok(val) => Ok(val),
-                Err(_) => Err(ConvertStringToIntError),
-            }
-        },
-        Err(_) => Err(IoReadError),
-    }
-}
-
 pub fn get_input_string() -> Result<String, ProcessingError> {
     let mut input = String::new();
     match io::stdin().read_line(&mut input) {
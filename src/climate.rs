@@ -0,0 +1,165 @@
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::config::ConfigError;
+use crate::weather::{Seasons, TemperatureRange, Transitions};
+
+/// Where the business is, which decides what weather it gets. Picked when a
+/// new business is started.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Climate {
+    #[default]
+    Temperate,
+    Tropical,
+    Desert,
+    Nordic,
+}
+
+impl Climate {
+    pub const ALL: [Climate; 4] = [Climate::Temperate, Climate::Tropical, Climate::Desert, Climate::Nordic];
+
+    pub fn describe(&self) -> String {
+        match self {
+            Climate::Temperate => "temperate".to_string(),
+            Climate::Tropical => "tropical".to_string(),
+            Climate::Desert => "desert".to_string(),
+            Climate::Nordic => "nordic".to_string(),
+        }
+    }
+
+    /// The name of this climate's data file.
+    pub fn file_name(&self) -> String { format!("{}.ron", self.describe()) }
+
+    /// The profile that comes with the game.
+    pub fn profile(&self) -> ClimateProfile {
+        let text = match self {
+            Climate::Temperate => include_str!("../climates/temperate.ron"),
+            Climate::Tropical => include_str!("../climates/tropical.ron"),
+            Climate::Desert => include_str!("../climates/desert.ron"),
+            Climate::Nordic => include_str!("../climates/nordic.ron"),
+        };
+        ClimateProfile::from_ron(text).expect("the built-in climates are valid")
+    }
+}
+
+/// Everything about the weather that changes from one climate to another.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ClimateProfile {
+    pub climate: Climate,
+    /// How many days each season lasts.
+    pub season_days: Seasons<u8>,
+    /// How warm each season usually is, in °C.
+    pub temperatures: Seasons<TemperatureRange>,
+    /// What the weather does next in each season, given what it's doing now.
+    pub transitions: Seasons<Transitions>,
+}
+
+impl Default for ClimateProfile {
+    fn default() -> Self { Climate::Temperate.profile() }
+}
+
+impl ClimateProfile {
+    pub fn from_ron(ron_text: &str) -> Result<Self, ConfigError> {
+        let profile: ClimateProfile = ron::from_str(ron_text).map_err(ConfigError::Parse)?;
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Load `climate`'s data file from `dir`, or use the built-in profile when there isn't one.
+    pub fn load_or_builtin(dir: &str, climate: Climate) -> Result<Self, ConfigError> {
+        let file_path = Path::new(dir).join(climate.file_name());
+        let mut content = String::new();
+        match File::open(file_path) {
+            Ok(mut file) => { file.read_to_string(&mut content).map_err(ConfigError::Io)?; },
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(climate.profile()),
+            Err(err) => return Err(ConfigError::Io(err)),
+        }
+        Self::from_ron(content.as_str())
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+        let mut check = |ok: bool, problem: &str| if !ok { problems.push(problem.to_string()) };
+
+        for (season, days) in self.season_days.iter() {
+            check(days > 0, &format!("season_days.{} must be at least 1", season.describe()));
+        }
+        for (season, range) in self.temperatures.iter() {
+            check(range.mean.is_finite(), &format!("temperatures.{} mean must be a number", season.describe()));
+            check(range.spread >= 0.0, &format!("temperatures.{} spread must not be negative", season.describe()));
+        }
+        for (season, transitions) in self.transitions.iter() {
+            check(transitions.is_valid(), &format!(
+                "transitions.{} rows must have no negative chances and add up to 1", season.describe()));
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(ConfigError::Invalid(problems)) }
+    }
+}
+
+#[cfg(test)]
+mod climate_tests {
+    use crate::climate::{Climate, ClimateProfile};
+    use crate::config::ConfigError;
+    use crate::weather::{Season, WeatherType};
+
+    #[test]
+    fn built_in_climates_load() {
+        for climate in Climate::ALL {
+            let profile = climate.profile();
+            assert_eq!(profile.climate, climate);
+            assert!(profile.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn shipped_files_match_the_built_in_climates() {
+        for climate in Climate::ALL {
+            assert_eq!(ClimateProfile::load_or_builtin("climates", climate).unwrap(), climate.profile());
+        }
+        assert_eq!(ClimateProfile::load_or_builtin("no_such_dir", Climate::Desert).unwrap(), Climate::Desert.profile());
+    }
+
+    #[test]
+    fn climates_have_their_own_weather() {
+        let sunny = |climate: Climate, season: Season| {
+            climate.profile().transitions.get_ref(season).stationary().get(WeatherType::Sunny)
+        };
+        let snowy = |climate: Climate, season: Season| {
+            climate.profile().transitions.get_ref(season).stationary().get(WeatherType::Snowy)
+        };
+        assert!(sunny(Climate::Desert, Season::Summer) > sunny(Climate::Temperate, Season::Summer));
+        assert!(sunny(Climate::Tropical, Season::Summer) < sunny(Climate::Temperate, Season::Summer));
+        assert!(snowy(Climate::Nordic, Season::Winter) > snowy(Climate::Temperate, Season::Winter));
+        assert_eq!(snowy(Climate::Tropical, Season::Winter), 0.0);
+
+        let nordic = Climate::Nordic.profile();
+        assert!(nordic.season_days.winter > nordic.season_days.summer);
+        assert!(nordic.temperatures.winter.mean < Climate::Temperate.profile().temperatures.winter.mean);
+        assert!(Climate::Desert.profile().temperatures.summer.mean > Climate::Temperate.profile().temperatures.summer.mean);
+    }
+
+    #[test]
+    fn transitions_must_add_up() {
+        let mut profile = Climate::Temperate.profile();
+        profile.transitions.winter.stormy.stormy = 0.9;
+        profile.season_days.fall = 0;
+        match profile.validate() {
+            Err(ConfigError::Invalid(problems)) => {
+                assert_eq!(problems.len(), 2);
+                assert!(problems[1].contains("transitions.winter"));
+            },
+            other => panic!("Expected invalid climate, got {:?}", other),
+        }
+
+        let mut profile = Climate::Temperate.profile();
+        profile.temperatures.summer.mean = f32::NAN;
+        match profile.validate() {
+            Err(ConfigError::Invalid(problems)) => assert_eq!(problems, ["temperatures.summer mean must be a number"]),
+            other => panic!("Expected invalid climate, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{ErrorKind, Read};
+use serde::{Deserialize, Serialize};
+use crate::climate::ClimateProfile;
+use crate::demand::{DemandTable, LocationDemand, PeriodDemand, SegmentConfig, Segments};
+use crate::location::{Location, LocationConfig, Locations};
+use crate::period::Periods;
+
+/// Every number that tunes the game's economy, loaded from a RON file.
+///
+/// Any section or value left out of the file falls back to the built-in default.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct GameConfig {
+    pub prices: PriceConfig,
+    pub starting: StartingConfig,
+    pub weather: WeatherConfig,
+    pub demand: DemandConfig,
+    pub fleet: FleetConfig,
+    /// What it costs to open and run a shop at each location.
+    pub locations: Locations<LocationConfig>,
+    pub operating: OperatingConfig,
+    pub bank: BankConfig,
+    /// When the game is over.
+    pub ending: EndingConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct PriceConfig {
+    pub scooter: f32,
+    pub part: f32,
+    pub advert: f32,
+    /// Fraction of the new price a scooter in perfect condition sells for.
+    pub resale_fraction: f32,
+    /// Cost of moving one scooter from one location to another.
+    pub transfer: f32,
+}
+
+impl Default for PriceConfig {
+    fn default() -> Self {
+        Self { scooter: 100.0, part: 25.0, advert: 5.0, resale_fraction: 0.5, transfer: 5.0 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct StartingConfig {
+    pub cash: f32,
+    pub scooters: u32,
+    /// Where the first shop opens, free of charge.
+    pub location: Location,
+}
+
+impl Default for StartingConfig {
+    fn default() -> Self {
+        Self { cash: 100.0, scooters: 10, location: Location::Downtown }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct WeatherConfig {
+    /// Chance tomorrow's forecast is right.
+    pub forecast_accuracy: f32,
+    /// How much of its accuracy the forecast keeps for each day further ahead.
+    pub forecast_decay: f32,
+    /// Chance the weather changes from one part of the day to the next.
+    pub intraday_change: f32,
+    /// Comes from the climate's own data file, chosen with the business, rather than the config.
+    #[serde(skip)]
+    pub climate: ClimateProfile,
+}
+
+impl Default for WeatherConfig {
+    fn default() -> Self {
+        Self {
+            forecast_accuracy: 0.70,
+            forecast_decay: 0.85,
+            intraday_change: 0.2,
+            climate: ClimateProfile::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DemandConfig {
+    /// Potential customers a day in perfect weather with no advertising.
+    pub market_size: f32,
+    /// Extra share of the market brought in by each advertisement.
+    pub advert_effect: f32,
+    /// Scales demand by the weather and temperature. Readings between two
+    /// temperature bands are blended from both.
+    pub table: DemandTable,
+    /// Wind up to this strength puts nobody off.
+    pub calm_wind_kph: f32,
+    /// Share of customers lost for each km/h of wind above `calm_wind_kph`.
+    pub wind_drag: f32,
+    /// Share of customers lost for each mm of rain or snow.
+    pub rain_drag: f32,
+    /// The kinds of customer making up the market, and how each responds to price, weather and season.
+    pub segments: Segments<SegmentConfig>,
+    /// How the day's customers are spread over its periods.
+    pub periods: Periods<PeriodDemand>,
+    /// Who each location draws in.
+    pub locations: Locations<LocationDemand>,
+}
+
+impl Default for DemandConfig {
+    fn default() -> Self {
+        Self {
+            market_size: 20.0,
+            advert_effect: 0.1,
+            table: DemandTable::default(),
+            calm_wind_kph: 20.0,
+            wind_drag: 0.01,
+            rain_drag: 0.03,
+            segments: Segments::default(),
+            periods: Periods::default(),
+            locations: Locations::default(),
+        }
+    }
+}
+
+/// Running costs paid whether or not any scooters are rented, on top of each location's rent.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct OperatingConfig {
+    /// Paid every day for each scooter owned, working or not.
+    pub storage_per_scooter: f32,
+    /// Paid every day for the staff at each open shop.
+    pub wages_per_shop: f32,
+    /// Paid at the end of every season for each scooter owned.
+    pub insurance_per_scooter: f32,
+}
+
+impl Default for OperatingConfig {
+    fn default() -> Self {
+        Self { storage_per_scooter: 0.5, wages_per_shop: 10.0, insurance_per_scooter: 3.0 }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndingConfig {
+    /// Nights in a row the business can be worth less than nothing, or be past
+    /// its overdraft, before it goes bankrupt.
+    pub bankruptcy_days: u32,
+    /// The game ends after this day, if there is one.
+    pub last_day: Option<u32>,
+}
+
+impl Default for EndingConfig {
+    fn default() -> Self {
+        Self { bankruptcy_days: 3, last_day: None }
+    }
+}
+
+/// The terms the bank lends on.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct BankConfig {
+    /// Interest charged each day on what's left of a loan.
+    pub loan_daily_rate: f32,
+    /// Days a loan takes to pay back.
+    pub loan_term_days: u32,
+    /// Credit the bank gives any business, before looking at its takings.
+    pub base_credit: f32,
+    /// Extra credit for each dollar of rentals taken over the last `credit_history_days`.
+    pub revenue_multiple: f32,
+    pub credit_history_days: u32,
+    /// How far cash can go below zero to pay for things, if there's credit left.
+    pub overdraft_limit: f32,
+    /// Charged every night cash ends below zero.
+    pub overdraft_fee: f32,
+    /// Interest charged each night on however far cash is below zero.
+    pub overdraft_daily_rate: f32,
+}
+
+impl Default for BankConfig {
+    fn default() -> Self {
+        Self {
+            loan_daily_rate: 0.005,
+            loan_term_days: 28,
+            base_credit: 200.0,
+            revenue_multiple: 1.0,
+            credit_history_days: 14,
+            overdraft_limit: 100.0,
+            overdraft_fee: 2.0,
+            overdraft_daily_rate: 0.01,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct FleetConfig {
+    /// Chance a brand new scooter breaks on a single rental.
+    pub chance_scooter_breaks: f32,
+    /// A fully worn scooter is `1 + wear_breakdown_factor` times as likely to break.
+    pub wear_breakdown_factor: f32,
+    pub wear_per_km: f32,
+    /// Fraction of its wear a scooter keeps after being repaired.
+    pub repair_wear_kept: f32,
+    pub battery_per_km: f32,
+    pub overnight_charge: f32,
+    pub min_battery_to_rent: f32,
+    pub ride_km_min: f32,
+    pub ride_km_max: f32,
+    /// Extra share of breakdowns for each mm of rain or snow.
+    pub rain_breakdown_factor: f32,
+    /// Extra share of breakdowns for each °C below freezing.
+    pub cold_breakdown_factor: f32,
+}
+
+impl Default for FleetConfig {
+    fn default() -> Self {
+        Self {
+            chance_scooter_breaks: 0.05,
+            wear_breakdown_factor: 4.0,
+            wear_per_km: 0.002,
+            repair_wear_kept: 0.25,
+            battery_per_km: 0.04,
+            overnight_charge: 0.5,
+            min_battery_to_rent: 0.3,
+            ride_km_min: 5.0,
+            ride_km_max: 15.0,
+            rain_breakdown_factor: 0.02,
+            cold_breakdown_factor: 0.03,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "couldn't read the config file: {}", err),
+            ConfigError::Parse(err) => write!(f, "couldn't parse the config file: {}", err),
+            ConfigError::Invalid(problems) => {
+                write!(f, "the config file has invalid values:")?;
+                for problem in problems {
+                    write!(f, "\n\t{}", problem)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl GameConfig {
+    pub fn from_ron(ron_text: &str) -> Result<Self, ConfigError> {
+        let config: GameConfig = ron::from_str(ron_text).map_err(ConfigError::Parse)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(&self, ron::ser::PrettyConfig::default()).unwrap()
+    }
+
+    pub fn load(file_path: &str) -> Result<Self, ConfigError> {
+        let mut content = String::new();
+        let mut file = File::open(file_path).map_err(ConfigError::Io)?;
+        file.read_to_string(&mut content).map_err(ConfigError::Io)?;
+        Self::from_ron(content.as_str())
+    }
+
+    /// Load the config file, or use the built-in default when there isn't one.
+    pub fn load_or_default(file_path: &str) -> Result<Self, ConfigError> {
+        match Self::load(file_path) {
+            Err(ConfigError::Io(err)) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            other => other,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+        let mut check = |ok: bool, problem: &str| if !ok { problems.push(problem.to_string()) };
+        let fraction = |val: f32| (0.0..=1.0).contains(&val);
+
+        check(self.prices.scooter >= 0.0, "prices.scooter must not be negative");
+        check(self.prices.part >= 0.0, "prices.part must not be negative");
+        check(self.prices.advert >= 0.0, "prices.advert must not be negative");
+        check(fraction(self.prices.resale_fraction), "prices.resale_fraction must be between 0 and 1");
+        check(self.starting.cash >= 0.0, "starting.cash must not be negative");
+        check(fraction(self.weather.forecast_accuracy), "weather.forecast_accuracy must be between 0 and 1");
+        check(fraction(self.weather.forecast_decay), "weather.forecast_decay must be between 0 and 1");
+        check(fraction(self.weather.intraday_change), "weather.intraday_change must be between 0 and 1");
+        check(self.demand.market_size >= 0.0, "demand.market_size must not be negative");
+        check(self.demand.advert_effect >= 0.0, "demand.advert_effect must not be negative");
+        check(self.demand.table.all_non_negative(), "demand.table values must not be negative");
+        check(self.demand.calm_wind_kph >= 0.0, "demand.calm_wind_kph must not be negative");
+        check(self.demand.wind_drag >= 0.0, "demand.wind_drag must not be negative");
+        check(self.demand.rain_drag >= 0.0, "demand.rain_drag must not be negative");
+        for (segment, config) in self.demand.segments.iter() {
+            check(config.is_valid(), &format!(
+                "demand.segments.{} must have no negative values and a price_spread above 0", segment.describe()));
+        }
+        for (period, demand) in self.demand.periods.iter() {
+            check(demand.is_valid(), &format!("demand.periods.{:?} must have no negative values", period).to_lowercase());
+        }
+        for (location, demand) in self.demand.locations.iter() {
+            check(demand.is_valid(), &format!("demand.locations.{} must have no negative values", location.describe()));
+        }
+        for (location, costs) in self.locations.iter() {
+            check(costs.open_cost >= 0.0 && costs.rent >= 0.0,
+                  &format!("locations.{} costs must not be negative", location.describe()));
+        }
+        check(self.prices.transfer >= 0.0, "prices.transfer must not be negative");
+        check(self.operating.storage_per_scooter >= 0.0, "operating.storage_per_scooter must not be negative");
+        check(self.operating.wages_per_shop >= 0.0, "operating.wages_per_shop must not be negative");
+        check(self.operating.insurance_per_scooter >= 0.0, "operating.insurance_per_scooter must not be negative");
+        check(self.bank.loan_daily_rate >= 0.0, "bank.loan_daily_rate must not be negative");
+        check(self.bank.loan_term_days > 0, "bank.loan_term_days must be at least 1");
+        check(self.bank.base_credit >= 0.0, "bank.base_credit must not be negative");
+        check(self.bank.revenue_multiple >= 0.0, "bank.revenue_multiple must not be negative");
+        check(self.bank.overdraft_limit >= 0.0, "bank.overdraft_limit must not be negative");
+        check(self.bank.overdraft_fee >= 0.0, "bank.overdraft_fee must not be negative");
+        check(self.bank.overdraft_daily_rate >= 0.0, "bank.overdraft_daily_rate must not be negative");
+        check(self.ending.bankruptcy_days > 0, "ending.bankruptcy_days must be at least 1");
+        check(self.ending.last_day != Some(0), "ending.last_day must be at least 1");
+        check(fraction(self.fleet.chance_scooter_breaks), "fleet.chance_scooter_breaks must be between 0 and 1");
+        check(self.fleet.wear_breakdown_factor >= 0.0, "fleet.wear_breakdown_factor must not be negative");
+        check(self.fleet.wear_per_km >= 0.0, "fleet.wear_per_km must not be negative");
+        check(fraction(self.fleet.repair_wear_kept), "fleet.repair_wear_kept must be between 0 and 1");
+        check(self.fleet.battery_per_km >= 0.0, "fleet.battery_per_km must not be negative");
+        check(fraction(self.fleet.overnight_charge), "fleet.overnight_charge must be between 0 and 1");
+        check(fraction(self.fleet.min_battery_to_rent), "fleet.min_battery_to_rent must be between 0 and 1");
+        check(self.fleet.ride_km_min >= 0.0, "fleet.ride_km_min must not be negative");
+        check(self.fleet.ride_km_min < self.fleet.ride_km_max, "fleet.ride_km_min must be less than fleet.ride_km_max");
+        check(self.fleet.rain_breakdown_factor >= 0.0, "fleet.rain_breakdown_factor must not be negative");
+        check(self.fleet.cold_breakdown_factor >= 0.0, "fleet.cold_breakdown_factor must not be negative");
+
+        if problems.is_empty() { Ok(()) } else { Err(ConfigError::Invalid(problems)) }
+    }
+}
+
+#[cfg(test)]
+mod config_tests {
+    use crate::config::{ConfigError, GameConfig};
+
+    #[test]
+    fn shipped_config_matches_default() {
+        let shipped = GameConfig::from_ron(include_str!("../scooter_config.ron")).unwrap();
+        assert_eq!(shipped, GameConfig::default());
+    }
+
+    #[test]
+    fn partial_config_uses_defaults() {
+        let config = GameConfig::from_ron("(starting: (cash: 500.0))").unwrap();
+        assert_eq!(config.starting.cash, 500.0);
+        assert_eq!(config.starting.scooters, GameConfig::default().starting.scooters);
+        assert_eq!(config.prices, GameConfig::default().prices);
+    }
+
+    #[test]
+    fn misspelled_keys_are_reported() {
+        match GameConfig::from_ron("(prices: (scoter: 50.0))") {
+            Err(ConfigError::Parse(err)) => assert!(err.to_string().contains("scoter")),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+        assert!(matches!(GameConfig::from_ron("(weather: (transitions: ()))"), Err(ConfigError::Parse(_))));
+        let period = "(share: 0.25, segments: (commuters: 1.0, tourists: 1.0, students: 1.0, leisure: 1.0))";
+        let periods = format!("(demand: (periods: (morning: {0}, midday: {0}, evening: {0}, night: {0}, nite: {0})))", period);
+        match GameConfig::from_ron(&periods) {
+            Err(ConfigError::Parse(err)) => assert!(err.to_string().contains("nite")),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_values_are_all_reported() {
+        let text = "(weather: (intraday_change: -0.5, forecast_accuracy: 1.5))";
+        match GameConfig::from_ron(text) {
+            Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+            other => panic!("Expected invalid config, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn missing_file_uses_default() {
+        let config = GameConfig::load_or_default("no_such_config_file.ron").unwrap();
+        assert_eq!(config, GameConfig::default());
+    }
+}
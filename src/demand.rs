@@ -0,0 +1,624 @@
+use std::fmt::Debug;
+use std::iter::Sum;
+use serde::{Deserialize, Serialize};
+use crate::config::DemandConfig;
+use crate::location::{Location, Locations};
+use crate::period::{Period, Periods};
+use crate::weather::{Conditions, Season, Temperature, WeatherType};
+
+/// The kinds of customer who rent scooters.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Segment {
+    Commuters,
+    Tourists,
+    Students,
+    Leisure,
+}
+
+impl Segment {
+    pub fn describe(&self) -> String {
+        match self {
+            Segment::Commuters => "commuters".to_string(),
+            Segment::Tourists => "tourists".to_string(),
+            Segment::Students => "students".to_string(),
+            Segment::Leisure => "leisure".to_string(),
+        }
+    }
+}
+
+/// One value for each customer `Segment`.
+///
+/// Like `WeatherDemand`, every segment has its own field, so a new segment has
+/// to be given a value everywhere before the game will compile.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Segments<T> {
+    pub commuters: T,
+    pub tourists: T,
+    pub students: T,
+    pub leisure: T,
+}
+
+impl<T: Copy> Segments<T> {
+    pub fn get(&self, segment: Segment) -> T {
+        match segment {
+            Segment::Commuters => self.commuters,
+            Segment::Tourists => self.tourists,
+            Segment::Students => self.students,
+            Segment::Leisure => self.leisure,
+        }
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Segment, T) -> U) -> Segments<U> {
+        Segments {
+            commuters: f(Segment::Commuters, self.commuters),
+            tourists: f(Segment::Tourists, self.tourists),
+            students: f(Segment::Students, self.students),
+            leisure: f(Segment::Leisure, self.leisure),
+        }
+    }
+
+    /// Each segment with its value, always in the same order.
+    pub fn iter(&self) -> impl Iterator<Item = (Segment, T)> {
+        [
+            (Segment::Commuters, self.commuters),
+            (Segment::Tourists, self.tourists),
+            (Segment::Students, self.students),
+            (Segment::Leisure, self.leisure),
+        ].into_iter()
+    }
+
+    pub fn total(&self) -> T where T: Sum {
+        self.iter().map(|(_, val)| val).sum()
+    }
+}
+
+impl Segments<f32> {
+    /// Turn expected customers into whole rentals, never handing out more than
+    /// `available` scooters.
+    ///
+    /// The total is rounded down once rather than per segment, so small
+    /// segments aren't rounded away. When there are more customers than
+    /// scooters every segment loses the same share of its customers. Either
+    /// way the rentals left over by rounding go to the segments with the
+    /// largest remainders.
+    pub fn share_out(&self, available: u32) -> Segments<u32> {
+        let wanted = self.map(|_, val| val.max(0.0));
+        let total = wanted.total();
+        let target = (total.floor() as u32).min(available);
+        let scale = if total > 0.0 { target as f32 / total } else { 0.0 };
+        let mut rented = wanted.map(|_, val| (val * scale).floor() as u32);
+        let mut remainders: Vec<(Segment, f32)> = wanted.iter()
+            .map(|(segment, val)| (segment, val * scale - rented.get(segment) as f32))
+            .collect();
+        remainders.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let short = target.saturating_sub(rented.total()) as usize;
+        for (segment, _) in remainders.into_iter().take(short) {
+            *rented.get_mut(segment) += 1;
+        }
+        rented
+    }
+}
+
+impl<T> Segments<T> {
+    pub fn get_mut(&mut self, segment: Segment) -> &mut T {
+        match segment {
+            Segment::Commuters => &mut self.commuters,
+            Segment::Tourists => &mut self.tourists,
+            Segment::Students => &mut self.students,
+            Segment::Leisure => &mut self.leisure,
+        }
+    }
+}
+
+/// How much of a segment is around in each season, 1.0 being all of it.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SeasonPresence {
+    pub spring: f32,
+    pub summer: f32,
+    pub fall: f32,
+    pub winter: f32,
+}
+
+impl SeasonPresence {
+    pub fn get(&self, season: Season) -> f32 {
+        match season {
+            Season::Spring => self.spring,
+            Season::Summer => self.summer,
+            Season::Fall => self.fall,
+            Season::Winter => self.winter,
+        }
+    }
+}
+
+/// What one kind of customer is like.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SegmentConfig {
+    /// Share of `market_size` made up of this segment.
+    pub share: f32,
+    /// Price at which half of this segment would still rent.
+    pub median_reservation_price: f32,
+    /// How spread out this segment's reservation prices are; larger is less price sensitive.
+    pub price_spread: f32,
+    /// How willing this segment is to ride in each kind of weather, on top of the demand table.
+    pub weather: WeatherDemand,
+    pub seasons: SeasonPresence,
+}
+
+impl SegmentConfig {
+    /// Share of this segment whose reservation price is at or above `cost_per`.
+    pub fn share_willing(&self, cost_per: f32) -> f32 {
+        let z = (cost_per - self.median_reservation_price) / self.price_spread;
+        1.0 / (1.0 + z.exp())
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let weather = self.weather;
+        let seasons = self.seasons;
+        self.share >= 0.0 && self.median_reservation_price >= 0.0 && self.price_spread > 0.0
+            && [weather.sunny, weather.cloudy, weather.rainy, weather.stormy, weather.snowy].iter().all(|val| *val >= 0.0)
+            && [seasons.spring, seasons.summer, seasons.fall, seasons.winter].iter().all(|val| *val >= 0.0)
+    }
+}
+
+impl Default for Segments<SegmentConfig> {
+    fn default() -> Self {
+        Self {
+            // Ride whatever the weather and don't much mind the price, but fewer are about in summer
+            commuters: SegmentConfig {
+                share: 0.35,
+                median_reservation_price: 15.0,
+                price_spread: 6.0,
+                weather: WeatherDemand { sunny: 1.0, cloudy: 1.0, rainy: 0.7, stormy: 0.3, snowy: 0.5 },
+                seasons: SeasonPresence { spring: 1.0, summer: 0.8, fall: 1.0, winter: 1.0 },
+            },
+            // Will pay well for a sunny ride, mostly in summer
+            tourists: SegmentConfig {
+                share: 0.25,
+                median_reservation_price: 20.0,
+                price_spread: 5.0,
+                weather: WeatherDemand { sunny: 1.0, cloudy: 0.8, rainy: 0.3, stormy: 0.1, snowy: 0.3 },
+                seasons: SeasonPresence { spring: 0.6, summer: 1.0, fall: 0.5, winter: 0.2 },
+            },
+            // Very price sensitive, and mostly gone over the summer break
+            students: SegmentConfig {
+                share: 0.2,
+                median_reservation_price: 8.0,
+                price_spread: 2.0,
+                weather: WeatherDemand { sunny: 1.0, cloudy: 0.9, rainy: 0.5, stormy: 0.2, snowy: 0.4 },
+                seasons: SeasonPresence { spring: 1.0, summer: 0.3, fall: 1.0, winter: 0.8 },
+            },
+            // Fair-weather riders
+            leisure: SegmentConfig {
+                share: 0.2,
+                median_reservation_price: 15.0,
+                price_spread: 4.0,
+                weather: WeatherDemand { sunny: 1.0, cloudy: 0.7, rainy: 0.2, stormy: 0.05, snowy: 0.2 },
+                seasons: SeasonPresence { spring: 0.9, summer: 1.0, fall: 0.7, winter: 0.3 },
+            },
+        }
+    }
+}
+
+/// Who is out and about during one period of the day.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PeriodDemand {
+    /// Share of the day's market looking for a ride in this period.
+    pub share: f32,
+    /// How much more or less of each segment is out in this period than usual.
+    pub segments: Segments<f32>,
+}
+
+impl PeriodDemand {
+    pub fn is_valid(&self) -> bool {
+        self.share >= 0.0 && self.segments.iter().all(|(_, val)| val >= 0.0)
+    }
+}
+
+impl Default for Periods<PeriodDemand> {
+    fn default() -> Self {
+        Self {
+            // Commuters heading to work and students to class
+            morning: PeriodDemand {
+                share: 0.3,
+                segments: Segments { commuters: 1.8, tourists: 0.4, students: 1.0, leisure: 0.3 },
+            },
+            // Sightseers and people out enjoying the day
+            midday: PeriodDemand {
+                share: 0.3,
+                segments: Segments { commuters: 0.3, tourists: 1.5, students: 1.0, leisure: 1.5 },
+            },
+            // Everyone heading home or out for the evening
+            evening: PeriodDemand {
+                share: 0.3,
+                segments: Segments { commuters: 1.5, tourists: 1.0, students: 1.0, leisure: 1.0 },
+            },
+            // A few night owls
+            night: PeriodDemand {
+                share: 0.1,
+                segments: Segments { commuters: 0.2, tourists: 0.8, students: 1.5, leisure: 0.3 },
+            },
+        }
+    }
+}
+
+/// Who a location draws in.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LocationDemand {
+    /// This location's market, as a multiple of `market_size`.
+    pub market_size: f32,
+    /// How much more or less of each segment comes by than usual.
+    pub segments: Segments<f32>,
+}
+
+impl LocationDemand {
+    pub fn is_valid(&self) -> bool {
+        self.market_size >= 0.0 && self.segments.iter().all(|(_, val)| val >= 0.0)
+    }
+}
+
+impl Default for Locations<LocationDemand> {
+    fn default() -> Self {
+        Self {
+            // Busy all year, mostly with people going to work
+            downtown: LocationDemand {
+                market_size: 1.0,
+                segments: Segments { commuters: 1.5, tourists: 1.0, students: 0.5, leisure: 0.8 },
+            },
+            // Tourists and day trippers
+            beach: LocationDemand {
+                market_size: 0.8,
+                segments: Segments { commuters: 0.3, tourists: 2.0, students: 0.8, leisure: 1.8 },
+            },
+            // Cheap rides between classes
+            campus: LocationDemand {
+                market_size: 0.7,
+                segments: Segments { commuters: 0.5, tourists: 0.2, students: 3.0, leisure: 0.6 },
+            },
+        }
+    }
+}
+
+/// Everything about one period of the day at one location a demand model can take into account.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DemandContext {
+    pub location: Location,
+    pub period: Period,
+    pub temperature: Temperature,
+    /// The weather during this period.
+    pub weather: WeatherType,
+    /// The day's readings the temperature came from.
+    pub conditions: Conditions,
+    pub season: Season,
+    /// Advertisements bought for the day at this location.
+    pub advertisements: u32,
+    /// Scooters put out for rent at this location.
+    pub deployed: u32,
+}
+
+/// Works out how many customers want a scooter at a given price.
+///
+/// The business caps rentals at the number of scooters it put out, so a model
+/// only has to describe the customers, not the fleet.
+pub trait DemandModel: Debug + Send + Sync {
+    /// Expected number of customers from each segment willing to pay
+    /// `cost_per` during the context's period at its location. Must be finite
+    /// and non-negative for any non-negative price.
+    fn expected_customers(&self, context: &DemandContext, cost_per: f32) -> Segments<f32>;
+}
+
+/// The default demand model: a pool of potential customers scaled by the
+/// weather, location and advertising, split into segments and spread over the
+/// day, each customer only renting below their own reservation price.
+///
+/// Each segment's reservation prices follow a logistic distribution around
+/// its `median_reservation_price`, so the share still willing to rent falls
+/// smoothly from nearly everyone at a price of 0 to nobody at a very high
+/// price. Raising the price always loses some customers, and nothing divides
+/// by the price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElasticDemand {
+    config: DemandConfig,
+}
+
+impl ElasticDemand {
+    pub fn new(config: DemandConfig) -> Self {
+        Self { config }
+    }
+
+    /// Customers who would come out at this location in this period's weather
+    /// if rentals were free.
+    pub fn market_size(&self, context: &DemandContext) -> Segments<f32> {
+        let adverts = context.advertisements.min(context.deployed) as f32;
+        let advert_boost = 1.0 + self.config.advert_effect * adverts;
+        let period = self.config.periods.get(context.period);
+        let location = self.config.locations.get(context.location);
+        let conditions = context.conditions;
+        let wind = (conditions.wind_kph - self.config.calm_wind_kph).max(0.0);
+        let wind_effect = (1.0 - self.config.wind_drag * wind).max(0.0);
+        let rain_effect = (1.0 - self.config.rain_drag * conditions.rain_mm).max(0.0);
+        let market = self.config.market_size * location.market_size * period.share * advert_boost
+            * self.config.table.modifier_at(conditions.celsius, context.weather) * wind_effect * rain_effect;
+        self.config.segments.map(|name, segment| {
+            market * segment.share * period.segments.get(name) * location.segments.get(name)
+                * segment.weather.get(context.weather) * segment.seasons.get(context.season)
+        })
+    }
+}
+
+impl DemandModel for ElasticDemand {
+    fn expected_customers(&self, context: &DemandContext, cost_per: f32) -> Segments<f32> {
+        let market = self.market_size(context);
+        self.config.segments.map(|segment, config| market.get(segment) * config.share_willing(cost_per))
+    }
+}
+
+/// Demand multiplier for each kind of weather at a single temperature.
+///
+/// Every `WeatherType` has its own field, so adding a new variant will not
+/// compile until it is given a value here, and a data file missing it will
+/// fail to load.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WeatherDemand {
+    pub sunny: f32,
+    pub cloudy: f32,
+    pub rainy: f32,
+    pub stormy: f32,
+    pub snowy: f32,
+}
+
+impl WeatherDemand {
+    pub fn get(&self, weather: WeatherType) -> f32 {
+        match weather {
+            WeatherType::Sunny => self.sunny,
+            WeatherType::Cloudy => self.cloudy,
+            WeatherType::Rainy => self.rainy,
+            WeatherType::Stormy => self.stormy,
+            WeatherType::Snowy => self.snowy,
+        }
+    }
+}
+
+/// How much the weather scales rental demand, for every `Temperature` x `WeatherType` pair.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DemandTable {
+    pub scorching: WeatherDemand,
+    pub hot: WeatherDemand,
+    pub warm: WeatherDemand,
+    pub cool: WeatherDemand,
+    pub cold: WeatherDemand,
+    pub freezing: WeatherDemand,
+}
+
+impl DemandTable {
+    pub fn row(&self, temperature: Temperature) -> &WeatherDemand {
+        match temperature {
+            Temperature::Scorching => &self.scorching,
+            Temperature::Hot => &self.hot,
+            Temperature::Warm => &self.warm,
+            Temperature::Cool => &self.cool,
+            Temperature::Cold => &self.cold,
+            Temperature::Freezing => &self.freezing,
+        }
+    }
+
+    pub fn modifier(&self, temperature: Temperature, weather: WeatherType) -> f32 {
+        self.row(temperature).get(weather)
+    }
+
+    /// The modifier at a reading in °C, blended between the two bands it falls
+    /// between by how close it is to the middle of each.
+    pub fn modifier_at(&self, celsius: f32, weather: WeatherType) -> f32 {
+        let warmest = Temperature::ALL[0];
+        if celsius >= warmest.typical_celsius() {
+            return self.modifier(warmest, weather);
+        }
+        for pair in Temperature::ALL.windows(2) {
+            let (warmer, colder) = (pair[0].typical_celsius(), pair[1].typical_celsius());
+            if celsius >= colder {
+                let warmth = (celsius - colder) / (warmer - colder);
+                return self.modifier(pair[1], weather) * (1.0 - warmth) + self.modifier(pair[0], weather) * warmth;
+            }
+        }
+        self.modifier(Temperature::Freezing, weather)
+    }
+
+    pub fn all_non_negative(&self) -> bool {
+        [self.scorching, self.hot, self.warm, self.cool, self.cold, self.freezing].iter()
+            .all(|row| [row.sunny, row.cloudy, row.rainy, row.stormy, row.snowy].iter().all(|val| *val >= 0.0))
+    }
+}
+
+impl Default for DemandTable {
+    fn default() -> Self {
+        Self {
+            scorching: WeatherDemand { sunny: 0.05, cloudy: 0.25, rainy: 0.10, stormy: 0.00, snowy: 0.00 },
+            hot: WeatherDemand { sunny: 0.50, cloudy: 0.60, rainy: 0.10, stormy: 0.05, snowy: 0.05 },
+            warm: WeatherDemand { sunny: 1.00, cloudy: 0.90, rainy: 0.20, stormy: 0.05, snowy: 0.10 },
+            cool: WeatherDemand { sunny: 1.00, cloudy: 0.90, rainy: 0.20, stormy: 0.05, snowy: 0.15 },
+            cold: WeatherDemand { sunny: 0.60, cloudy: 0.50, rainy: 0.10, stormy: 0.05, snowy: 0.15 },
+            freezing: WeatherDemand { sunny: 0.25, cloudy: 0.10, rainy: 0.05, stormy: 0.00, snowy: 0.05 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod elastic_demand_tests {
+    use crate::config::DemandConfig;
+    use crate::demand::{DemandContext, DemandModel, ElasticDemand, Segment, Segments};
+    use crate::location::Location;
+    use crate::period::Period;
+    use crate::weather::{Conditions, Season, Temperature, WeatherType};
+
+    /// Still and dry, in the middle of the temperature's band.
+    fn context(temperature: Temperature, weather: WeatherType) -> DemandContext {
+        DemandContext {
+            location: Location::Downtown,
+            period: Period::Midday,
+            temperature,
+            weather,
+            conditions: Conditions { celsius: temperature.typical_celsius(), wind_kph: 0.0, rain_mm: 0.0, cloud_cover: 0.0 },
+            season: Season::Spring,
+            advertisements: 0,
+            deployed: 10,
+        }
+    }
+
+    #[test]
+    fn free_rentals_are_finite() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let customers = model.expected_customers(&context(Temperature::Warm, WeatherType::Sunny), 0.0).total();
+        assert!(customers.is_finite());
+        assert!(customers > 0.0);
+        assert!(customers <= DemandConfig::default().market_size);
+    }
+
+    #[test]
+    fn higher_prices_lose_customers() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let ctx = context(Temperature::Warm, WeatherType::Sunny);
+        let mut last = f32::MAX;
+        for price in [0.0, 5.0, 10.0, 15.0, 20.0, 40.0, 1000.0] {
+            let customers = model.expected_customers(&ctx, price).total();
+            assert!(customers >= 0.0 && customers < last, "{} at ${}", customers, price);
+            last = customers;
+        }
+        assert!(last < 0.01);
+    }
+
+    #[test]
+    fn segments_feel_prices_differently() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let ctx = context(Temperature::Warm, WeatherType::Sunny);
+        let (cheap, dear) = (model.expected_customers(&ctx, 5.0), model.expected_customers(&ctx, 18.0));
+        let kept = |segment| dear.get(segment) / cheap.get(segment);
+        assert!(kept(Segment::Tourists) > kept(Segment::Students) * 5.0);
+    }
+
+    #[test]
+    fn tourists_stay_home_in_winter() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let summer = DemandContext { season: Season::Summer, ..context(Temperature::Cool, WeatherType::Sunny) };
+        let winter = DemandContext { season: Season::Winter, ..summer };
+        let (summer, winter) = (model.expected_customers(&summer, 15.0), model.expected_customers(&winter, 15.0));
+        assert!(winter.tourists < summer.tourists);
+        assert!(winter.commuters > summer.commuters);
+    }
+
+    #[test]
+    fn commuters_ride_in_the_rush_hour() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let morning = DemandContext { period: Period::Morning, ..context(Temperature::Warm, WeatherType::Sunny) };
+        let midday = model.expected_customers(&context(Temperature::Warm, WeatherType::Sunny), 12.0);
+        let morning = model.expected_customers(&morning, 12.0);
+        assert!(morning.commuters > midday.commuters);
+        assert!(morning.tourists < midday.tourists);
+    }
+
+    #[test]
+    fn students_go_to_campus() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let downtown = context(Temperature::Warm, WeatherType::Sunny);
+        let campus = DemandContext { location: Location::Campus, ..downtown };
+        let (downtown, campus) = (model.expected_customers(&downtown, 8.0), model.expected_customers(&campus, 8.0));
+        assert!(campus.students > downtown.students);
+        assert!(campus.commuters < downtown.commuters);
+    }
+
+    #[test]
+    fn nobody_rides_in_a_scorching_storm() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        assert_eq!(model.expected_customers(&context(Temperature::Scorching, WeatherType::Stormy), 5.0).total(), 0.0);
+    }
+
+    #[test]
+    fn wind_and_rain_keep_customers_away() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let still = context(Temperature::Warm, WeatherType::Cloudy);
+        let breezy = DemandContext { conditions: Conditions { wind_kph: 15.0, ..still.conditions }, ..still };
+        let gusty = DemandContext { conditions: Conditions { wind_kph: 45.0, ..still.conditions }, ..still };
+        let drizzly = DemandContext { conditions: Conditions { rain_mm: 0.5, ..still.conditions }, ..still };
+        let market = |context: &DemandContext| model.market_size(context).total();
+        assert_eq!(market(&breezy), market(&still));
+        assert!(market(&gusty) < market(&still));
+        assert!(market(&drizzly) < market(&still));
+    }
+
+    #[test]
+    fn adverts_only_count_for_deployed_scooters() {
+        let model = ElasticDemand::new(DemandConfig::default());
+        let few = DemandContext { advertisements: 20, deployed: 2, ..context(Temperature::Warm, WeatherType::Sunny) };
+        let capped = DemandContext { advertisements: 2, ..few };
+        assert_eq!(model.market_size(&few), model.market_size(&capped));
+    }
+
+    #[test]
+    fn share_out_keeps_everyone_when_there_is_room() {
+        let wanted = Segments { commuters: 3.7, tourists: 1.2, students: 0.0, leisure: 2.9 };
+        assert_eq!(wanted.share_out(10), Segments { commuters: 3, tourists: 1, students: 0, leisure: 3 });
+        let trickle = Segments { commuters: 0.6, tourists: 0.6, students: 0.0, leisure: 0.0 };
+        assert_eq!(trickle.share_out(10).total(), 1);
+    }
+
+    #[test]
+    fn share_out_never_exceeds_the_scooters() {
+        let wanted = Segments { commuters: 9.0, tourists: 6.0, students: 3.0, leisure: 2.0 };
+        let rented = wanted.share_out(7);
+        assert_eq!(rented.total(), 7);
+        assert!(rented.commuters >= rented.tourists && rented.tourists >= rented.students);
+        assert_eq!(wanted.share_out(0).total(), 0);
+    }
+}
+
+#[cfg(test)]
+mod demand_table_tests {
+    use crate::demand::DemandTable;
+    use crate::weather::{Temperature, WeatherType};
+
+    #[test]
+    fn every_pair_is_a_valid_modifier() {
+        let table = DemandTable::default();
+        let temperatures = [Temperature::Scorching, Temperature::Hot, Temperature::Warm,
+            Temperature::Cool, Temperature::Cold, Temperature::Freezing];
+        let weathers = [WeatherType::Sunny, WeatherType::Cloudy, WeatherType::Rainy,
+            WeatherType::Stormy, WeatherType::Snowy];
+        for temperature in temperatures {
+            for weather in weathers {
+                let val = table.modifier(temperature, weather);
+                assert!((0.0..=1.0).contains(&val), "{:?}/{:?} gave {}", temperature, weather, val);
+            }
+        }
+    }
+
+    #[test]
+    fn readings_between_bands_blend_them() {
+        let table = DemandTable::default();
+        let cold = table.modifier(Temperature::Cold, WeatherType::Sunny);
+        let cool = table.modifier(Temperature::Cool, WeatherType::Sunny);
+        assert_eq!(table.modifier_at(Temperature::Cool.typical_celsius(), WeatherType::Sunny), cool);
+        let between = (Temperature::Cold.typical_celsius() + Temperature::Cool.typical_celsius()) / 2.0;
+        assert!((table.modifier_at(between, WeatherType::Sunny) - (cold + cool) / 2.0).abs() < 0.0001);
+        assert_eq!(table.modifier_at(60.0, WeatherType::Sunny), table.modifier(Temperature::Scorching, WeatherType::Sunny));
+        assert_eq!(table.modifier_at(-40.0, WeatherType::Snowy), table.modifier(Temperature::Freezing, WeatherType::Snowy));
+    }
+
+    #[test]
+    fn snow_has_demand_entries() {
+        let table = DemandTable::default();
+        assert_eq!(table.modifier(Temperature::Cold, WeatherType::Snowy), 0.15);
+        assert_eq!(table.modifier(Temperature::Freezing, WeatherType::Snowy), 0.05);
+    }
+
+    #[test]
+    fn missing_weather_fails_to_load() {
+        let text = "(sunny: 1.0, cloudy: 1.0, rainy: 1.0, stormy: 1.0)";
+        assert!(ron::from_str::<crate::demand::WeatherDemand>(text).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use crate::config::GameConfig;
+
+/// How much each part of the config is scaled by. 1.0 leaves it as it is.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DifficultySettings {
+    pub starting_cash: f32,
+    /// Scales what scooters and parts cost.
+    pub prices: f32,
+    pub forecast_accuracy: f32,
+    pub breakage: f32,
+    /// Scales the size of the market.
+    pub demand: f32,
+}
+
+impl DifficultySettings {
+    pub const NORMAL: Self = Self { starting_cash: 1.0, prices: 1.0, forecast_accuracy: 1.0, breakage: 1.0, demand: 1.0 };
+
+    pub fn is_valid(&self) -> bool {
+        [self.starting_cash, self.prices, self.forecast_accuracy, self.breakage, self.demand]
+            .iter()
+            .all(|val| val.is_finite() && *val >= 0.0)
+    }
+}
+
+/// Chosen when a new game starts, and kept with it for good.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Custom(DifficultySettings),
+}
+
+impl Difficulty {
+    pub const PRESETS: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn describe(&self) -> String {
+        match self {
+            Difficulty::Easy => "easy".to_string(),
+            Difficulty::Normal => "normal".to_string(),
+            Difficulty::Hard => "hard".to_string(),
+            Difficulty::Custom(_) => "custom".to_string(),
+        }
+    }
+
+    /// Custom settings can come from a hand edited save, so may be nonsense.
+    pub fn is_valid(&self) -> bool { self.settings().is_valid() }
+
+    pub fn settings(&self) -> DifficultySettings {
+        match self {
+            Difficulty::Easy => DifficultySettings {
+                starting_cash: 2.0, prices: 0.8, forecast_accuracy: 1.2, breakage: 0.5, demand: 1.2,
+            },
+            Difficulty::Normal => DifficultySettings::NORMAL,
+            Difficulty::Hard => DifficultySettings {
+                starting_cash: 0.5, prices: 1.25, forecast_accuracy: 0.75, breakage: 1.5, demand: 0.8,
+            },
+            Difficulty::Custom(settings) => *settings,
+        }
+    }
+
+    /// The config with this difficulty's adjustments made. Chances are kept to at most 1.
+    pub fn apply(&self, config: &GameConfig) -> GameConfig {
+        let settings = self.settings();
+        let mut config = config.clone();
+        config.starting.cash *= settings.starting_cash;
+        config.prices.scooter *= settings.prices;
+        config.prices.part *= settings.prices;
+        config.weather.forecast_accuracy = (config.weather.forecast_accuracy * settings.forecast_accuracy).min(1.0);
+        config.fleet.chance_scooter_breaks = (config.fleet.chance_scooter_breaks * settings.breakage).min(1.0);
+        config.demand.market_size *= settings.demand;
+        config
+    }
+}
+
+#[cfg(test)]
+mod difficulty_tests {
+    use crate::config::GameConfig;
+    use crate::difficulty::{Difficulty, DifficultySettings};
+
+    #[test]
+    fn normal_changes_nothing() {
+        let config = GameConfig::default();
+        assert_eq!(Difficulty::Normal.apply(&config), config);
+    }
+
+    #[test]
+    fn hard_is_harder_than_easy() {
+        let config = GameConfig::default();
+        let easy = Difficulty::Easy.apply(&config);
+        let hard = Difficulty::Hard.apply(&config);
+        assert!(easy.starting.cash > hard.starting.cash);
+        assert!(easy.prices.scooter < hard.prices.scooter);
+        assert!(easy.weather.forecast_accuracy > hard.weather.forecast_accuracy);
+        assert!(easy.fleet.chance_scooter_breaks < hard.fleet.chance_scooter_breaks);
+        assert!(easy.demand.market_size > hard.demand.market_size);
+        assert!(easy.validate().is_ok() && hard.validate().is_ok());
+    }
+
+    #[test]
+    fn chances_stay_chances() {
+        let settings = DifficultySettings { forecast_accuracy: 5.0, breakage: 100.0, ..DifficultySettings::NORMAL };
+        let config = Difficulty::Custom(settings).apply(&GameConfig::default());
+        assert_eq!(config.weather.forecast_accuracy, 1.0);
+        assert_eq!(config.fleet.chance_scooter_breaks, 1.0);
+    }
+
+    #[test]
+    fn custom_settings_round_trip_through_ron() {
+        let difficulty = Difficulty::Custom(DifficultySettings { demand: 3.0, ..DifficultySettings::NORMAL });
+        let text = ron::to_string(&difficulty).unwrap();
+        assert_eq!(ron::from_str::<Difficulty>(&text).unwrap(), difficulty);
+    }
+}
@@ -0,0 +1,282 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::config::FleetConfig;
+use crate::weather::Conditions;
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum ScooterStatus {
+    Working,
+    Broken,
+}
+
+/// A single scooter in the fleet.
+///
+/// Wear and battery charge are both kept between 0.0 and 1.0.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct Scooter {
+    id: u32,
+    purchase_day: u32,
+    mileage: f32,
+    wear: f32,
+    battery: f32,
+    status: ScooterStatus,
+}
+
+impl Scooter {
+    pub fn new(id: u32, purchase_day: u32) -> Self {
+        Self {
+            id,
+            purchase_day,
+            mileage: 0.0,
+            wear: 0.0,
+            battery: 1.0,
+            status: ScooterStatus::Working,
+        }
+    }
+
+    pub fn with_wear(mut self, wear: f32) -> Self { self.wear = wear.clamp(0.0, 1.0); self }
+    pub fn with_battery(mut self, battery: f32) -> Self { self.battery = battery.clamp(0.0, 1.0); self }
+    pub fn with_status(mut self, status: ScooterStatus) -> Self { self.status = status; self }
+
+    pub fn id(&self) -> u32 { self.id }
+    pub fn purchase_day(&self) -> u32 { self.purchase_day }
+    pub fn mileage(&self) -> f32 { self.mileage }
+    pub fn wear(&self) -> f32 { self.wear }
+    pub fn battery(&self) -> f32 { self.battery }
+    pub fn status(&self) -> ScooterStatus { self.status }
+
+    pub fn is_working(&self) -> bool { self.status == ScooterStatus::Working }
+    pub fn is_rentable(&self, config: &FleetConfig) -> bool {
+        self.is_working() && self.battery >= config.min_battery_to_rent
+    }
+    pub fn age(&self, today: u32) -> u32 { today.saturating_sub(self.purchase_day) }
+
+    /// Chance this scooter breaks on its next ride in the given `conditions`.
+    /// A worn out scooter is `1 + wear_breakdown_factor` times as likely to
+    /// break as a new one, and rain and frost make it likelier still.
+    pub fn breakdown_chance(&self, config: &FleetConfig, conditions: &Conditions) -> f32 {
+        let weather = 1.0 + config.rain_breakdown_factor * conditions.rain_mm
+            + config.cold_breakdown_factor * (-conditions.celsius).max(0.0);
+        (config.chance_scooter_breaks * (1.0 + config.wear_breakdown_factor * self.wear) * weather).min(1.0)
+    }
+
+    /// Send the scooter out on one rental. Returns true if it broke down.
+    pub fn ride(&mut self, conditions: &Conditions, rng: &mut impl Rng, config: &FleetConfig) -> bool {
+        let km = rng.gen_range(config.ride_km_min..config.ride_km_max);
+        let broke = rng.gen::<f32>() < self.breakdown_chance(config, conditions);
+        self.mileage += km;
+        self.wear = (self.wear + km * config.wear_per_km).min(1.0);
+        self.battery = (self.battery - km * config.battery_per_km).max(0.0);
+        if broke { self.status = ScooterStatus::Broken; }
+        broke
+    }
+
+    pub fn recharge(&mut self, config: &FleetConfig) {
+        self.battery = (self.battery + config.overnight_charge).min(1.0);
+    }
+
+    /// Fix a broken scooter, taking off most of its wear.
+    pub fn repair(&mut self, config: &FleetConfig) {
+        self.status = ScooterStatus::Working;
+        self.wear *= config.repair_wear_kept;
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct Fleet {
+    scooters: Vec<Scooter>,
+    next_id: u32,
+}
+
+impl Fleet {
+    pub fn new(num: u32, day: u32) -> Self {
+        let mut fleet = Self::default();
+        fleet.add(num, day);
+        fleet
+    }
+
+    pub fn scooters(&self) -> &[Scooter] { &self.scooters }
+    pub fn len(&self) -> usize { self.scooters.len() }
+    pub fn is_empty(&self) -> bool { self.scooters.is_empty() }
+    pub fn working(&self) -> u32 { self.scooters.iter().filter(|s| s.is_working()).count() as u32 }
+    pub fn broken(&self) -> u32 { self.scooters.iter().filter(|s| !s.is_working()).count() as u32 }
+    pub fn rentable(&self, config: &FleetConfig) -> u32 {
+        self.scooters.iter().filter(|s| s.is_rentable(config)).count() as u32
+    }
+
+    pub fn add(&mut self, num: u32, day: u32) {
+        for _ in 0..num {
+            self.scooters.push(Scooter::new(self.next_id, day));
+            self.next_id += 1;
+        }
+    }
+
+    /// The id the next scooter bought will get.
+    pub fn next_id(&self) -> u32 { self.next_id }
+
+    /// Make sure scooters bought from now on get ids of at least `id`, so
+    /// several fleets can share one run of ids.
+    pub fn skip_ids_to(&mut self, id: u32) {
+        self.next_id = self.next_id.max(id);
+    }
+
+    pub fn push(&mut self, scooter: Scooter) {
+        self.next_id = self.next_id.max(scooter.id + 1);
+        self.scooters.push(scooter);
+    }
+
+    /// Remove `num` working scooters, most worn first, and hand them back.
+    pub fn remove_working(&mut self, num: u32) -> Vec<Scooter> {
+        let mut ids: Vec<(f32, u32)> = self.scooters.iter()
+            .filter(|s| s.is_working())
+            .map(|s| (s.wear, s.id))
+            .collect();
+        ids.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        let ids: Vec<u32> = ids.into_iter().take(num as usize).map(|(_, id)| id).collect();
+        let (removed, kept) = self.scooters.drain(..).partition(|s| ids.contains(&s.id));
+        self.scooters = kept;
+        removed
+    }
+
+    /// Repair `num` broken scooters, most worn first.
+    pub fn repair(&mut self, num: u32, config: &FleetConfig) {
+        let mut broken: Vec<&mut Scooter> = self.scooters.iter_mut().filter(|s| !s.is_working()).collect();
+        broken.sort_by(|a, b| b.wear.total_cmp(&a.wear));
+        for scooter in broken.into_iter().take(num as usize) {
+            scooter.repair(config);
+        }
+    }
+
+    /// Pick `num` rentable scooters to put out for the day, best charged first.
+    pub fn deploy(&self, num: u32, config: &FleetConfig) -> Vec<u32> {
+        let mut rentable: Vec<&Scooter> = self.scooters.iter().filter(|s| s.is_rentable(config)).collect();
+        rentable.sort_by(|a, b| b.battery.total_cmp(&a.battery));
+        rentable.into_iter().take(num as usize).map(|s| s.id).collect()
+    }
+
+    /// How many of the `deployed` scooters can still be rented.
+    pub fn rentable_of(&self, deployed: &[u32], config: &FleetConfig) -> u32 {
+        self.scooters.iter().filter(|s| deployed.contains(&s.id) && s.is_rentable(config)).count() as u32
+    }
+
+    /// Send out `num` of the `deployed` scooters that can still be rented,
+    /// best charged first, in the day's `conditions`. Returns how many broke down.
+    pub fn ride(
+        &mut self,
+        deployed: &[u32],
+        num: u32,
+        conditions: &Conditions,
+        rng: &mut impl Rng,
+        config: &FleetConfig,
+    ) -> u32 {
+        let mut rentable: Vec<&mut Scooter> = self.scooters.iter_mut()
+            .filter(|s| deployed.contains(&s.id) && s.is_rentable(config))
+            .collect();
+        rentable.sort_by(|a, b| b.battery.total_cmp(&a.battery));
+        rentable.into_iter()
+            .take(num as usize)
+            .map(|scooter| scooter.ride(conditions, rng, config))
+            .filter(|broke| *broke)
+            .count() as u32
+    }
+
+    pub fn recharge(&mut self, config: &FleetConfig) {
+        self.scooters.iter_mut().for_each(|scooter| scooter.recharge(config));
+    }
+
+    pub fn average_wear(&self) -> f32 { self.average(|s| s.wear) }
+    pub fn average_battery(&self) -> f32 { self.average(|s| s.battery) }
+    pub fn average_age(&self, today: u32) -> f32 { self.average(|s| s.age(today) as f32) }
+
+    fn average(&self, val: impl Fn(&Scooter) -> f32) -> f32 {
+        if self.scooters.is_empty() { return 0.0 }
+        self.scooters.iter().map(val).sum::<f32>() / self.scooters.len() as f32
+    }
+}
+
+#[cfg(test)]
+mod fleet_tests {
+    use crate::config::FleetConfig;
+    use crate::fleet::{Fleet, Scooter, ScooterStatus};
+    use crate::rng::seeded;
+    use crate::weather::{Conditions, Temperature, WeatherType};
+
+    fn mild() -> Conditions { Conditions::typical(WeatherType::Sunny, Temperature::Warm) }
+
+    #[test]
+    fn worn_scooters_break_more() {
+        let new = Scooter::new(0, 1);
+        let worn = Scooter::new(1, 1).with_wear(0.9);
+        let config = FleetConfig::default();
+        assert!(worn.breakdown_chance(&config, &mild()) > new.breakdown_chance(&config, &mild()));
+    }
+
+    #[test]
+    fn rain_and_frost_break_more() {
+        let scooter = Scooter::new(0, 1);
+        let config = FleetConfig::default();
+        let wet = Conditions { rain_mm: 10.0, ..mild() };
+        let frozen = Conditions { celsius: -10.0, ..mild() };
+        assert!(scooter.breakdown_chance(&config, &wet) > scooter.breakdown_chance(&config, &mild()));
+        assert!(scooter.breakdown_chance(&config, &frozen) > scooter.breakdown_chance(&config, &mild()));
+        assert_eq!(scooter.breakdown_chance(&config, &mild()), config.chance_scooter_breaks);
+    }
+
+    #[test]
+    fn repair_restores_condition() {
+        let mut scooter = Scooter::new(0, 1).with_wear(0.8).with_status(ScooterStatus::Broken);
+        scooter.repair(&FleetConfig::default());
+        assert!(scooter.is_working());
+        assert!(scooter.wear() < 0.8);
+    }
+
+    #[test]
+    fn riding_wears_and_drains() {
+        let mut scooter = Scooter::new(0, 1);
+        scooter.ride(&mild(), &mut seeded(1), &FleetConfig::default());
+        assert!(scooter.mileage() > 0.0);
+        assert!(scooter.wear() > 0.0);
+        assert!(scooter.battery() < 1.0);
+    }
+
+    #[test]
+    fn flat_scooters_are_not_rentable() {
+        let config = FleetConfig::default();
+        let mut fleet = Fleet::new(2, 1);
+        fleet.push(Scooter::new(5, 1).with_battery(0.0));
+        assert_eq!(fleet.working(), 3);
+        assert_eq!(fleet.rentable(&config), 2);
+        fleet.recharge(&config);
+        assert_eq!(fleet.rentable(&config), 3);
+    }
+
+    #[test]
+    fn sell_most_worn_first() {
+        let mut fleet = Fleet::new(2, 1);
+        fleet.push(Scooter::new(7, 1).with_wear(0.5));
+        let sold = fleet.remove_working(1);
+        assert_eq!(sold[0].id(), 7);
+        assert_eq!(fleet.len(), 2);
+    }
+
+    #[test]
+    fn only_deployed_scooters_ride() {
+        let config = FleetConfig::default();
+        let mut fleet = Fleet::new(4, 1);
+        fleet.push(Scooter::new(4, 1).with_battery(0.2));
+        let deployed = fleet.deploy(2, &config);
+        assert_eq!(deployed.len(), 2);
+        fleet.ride(&deployed, 10, &mild(), &mut seeded(2), &config);
+        let ridden: Vec<u32> = fleet.scooters().iter().filter(|s| s.mileage() > 0.0).map(|s| s.id()).collect();
+        assert_eq!(ridden, deployed);
+    }
+
+    #[test]
+    fn flat_scooters_drop_out_during_the_day() {
+        let config = FleetConfig { battery_per_km: 0.1, ride_km_min: 8.0, ..FleetConfig::default() };
+        let mut fleet = Fleet::new(3, 1);
+        let deployed = fleet.deploy(3, &config);
+        fleet.ride(&deployed, 3, &mild(), &mut seeded(2), &config);
+        assert_eq!(fleet.rentable_of(&deployed, &config), 0);
+    }
+}
@@ -0,0 +1,604 @@
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use crate::business::{Business, ManagementError, Receipt};
+use crate::climate::ClimateProfile;
+use crate::config::GameConfig;
+use crate::demand::{DemandModel, ElasticDemand};
+use crate::difficulty::Difficulty;
+use crate::history::{DayRecord, History};
+use crate::io::SaveFile;
+use crate::location::{Location, Locations};
+use crate::period::Periods;
+use crate::report::Report;
+use crate::rng::{seeded, GameRng};
+use crate::weather::Weather;
+
+/// Where in the day the game currently is.
+///
+/// A day always runs `DayStart` -> `Rental` -> `Management`, and ending the
+/// day from `Management` rolls over to the next `DayStart`, unless the game
+/// has ended, which leaves it `Over` for good.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Phase {
+    DayStart,
+    Rental,
+    Management,
+    Over,
+}
+
+/// Why the game ended.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum GameOver {
+    /// Worth less than nothing, or past the overdraft, for too many nights in a row.
+    Bankrupt,
+    /// No scooters left, and no way to pay for one.
+    OutOfScooters,
+    /// Made it to the last day in the config.
+    ReachedLastDay,
+}
+
+impl GameOver {
+    pub fn describe(&self) -> String {
+        match self {
+            GameOver::Bankrupt => "The business went bankrupt.".to_string(),
+            GameOver::OutOfScooters => "The business has no scooters left and can't afford any more.".to_string(),
+            GameOver::ReachedLastDay => "The business made it to the last day.".to_string(),
+        }
+    }
+}
+
+/// Everything a player (or a bot, or a test) can ask the game to do.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+pub enum Command {
+    StartDay,
+    /// Put scooters out at every open shop for the day, `deploy` of them at
+    /// each, charging that shop's `prices` in each period.
+    Rent { deploy: Locations<u32>, prices: Locations<Periods<f32>> },
+    BuyScooters { at: Location, num: u32 },
+    BuyParts(u32),
+    SellScooters { at: Location, num: u32 },
+    RepairScooters { at: Location, num: u32 },
+    BuyAdvertisements { at: Location, num: u32 },
+    OpenLocation(Location),
+    TransferScooters { from: Location, to: Location, num: u32 },
+    TakeLoan(f32),
+    /// Pay loans off early, up to the amount given.
+    RepayLoans(f32),
+    EndDay,
+}
+
+/// What happened as the result of a `Command`.
+#[derive(PartialEq, Debug)]
+pub enum Outcome {
+    DayStarted { day: u32, weather: Box<Weather> },
+    /// One receipt for each open shop.
+    Rented(Vec<Receipt>),
+    BoughtScooters { at: Location, num: u32, cost: f32 },
+    BoughtParts { num: u32, cost: f32 },
+    SoldScooters { at: Location, num: u32, income: f32 },
+    RepairedScooters { at: Location, num: u32 },
+    BoughtAdvertisements { at: Location, num: u32, cost: f32 },
+    OpenedLocation { at: Location, cost: f32 },
+    TransferredScooters { from: Location, to: Location, num: u32, cost: f32 },
+    TookLoan { amount: f32, daily_payment: f32, days: u32 },
+    RepaidLoans { amount: f32 },
+    /// `season_report` covers the whole season when this day was its last,
+    /// and `game_over` says why, if this day was the last of the game.
+    DayEnded { day: u32, season_report: Option<Report>, game_over: Option<GameOver> },
+}
+
+#[derive(PartialEq, Debug)]
+pub enum GameError {
+    WrongPhase { phase: Phase, command: Command },
+    Management(ManagementError),
+}
+
+impl From<ManagementError> for GameError {
+    fn from(err: ManagementError) -> Self { GameError::Management(err) }
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    day_num: u32,
+    phase: Phase,
+    seed: u64,
+    rng: GameRng,
+    business: Business,
+    weather: Weather,
+    difficulty: Difficulty,
+    /// The config with the difficulty's adjustments already made.
+    config: GameConfig,
+    /// Decides how many customers turn up each day; see `with_demand_model`.
+    demand: Arc<dyn DemandModel>,
+    history: History,
+    /// The day being played, filled in as the player makes decisions.
+    today: Option<DayRecord>,
+    /// Nights in a row the business has ended insolvent.
+    days_insolvent: u32,
+    game_over: Option<GameOver>,
+}
+
+impl Game {
+    pub fn new(name: String, seed: u64, config: GameConfig) -> Self {
+        Self::new_at_difficulty(name, seed, config, Difficulty::Normal)
+    }
+
+    /// A new game with `difficulty` adjusting the config for as long as it's played.
+    pub fn new_at_difficulty(name: String, seed: u64, config: GameConfig, difficulty: Difficulty) -> Self {
+        let config = difficulty.apply(&config);
+        let mut rng = seeded(seed);
+        let mut weather = Weather::new(config.weather);
+        weather.new_outlook(&mut rng);
+        Self {
+            day_num: 1,
+            phase: Phase::DayStart,
+            seed,
+            rng,
+            business: Business::new(name, &config),
+            weather,
+            difficulty,
+            demand: Arc::new(ElasticDemand::new(config.demand)),
+            config,
+            history: History::default(),
+            today: None,
+            days_insolvent: 0,
+            game_over: None,
+        }
+    }
+
+    /// Carry on a saved game. The difficulty it was started at is applied to
+    /// `config`, and its climate replaces the config's.
+    pub fn from_save_file(saved: SaveFile, mut config: GameConfig) -> Self {
+        config.weather.climate = saved.climate;
+        let config = saved.difficulty.apply(&config);
+        let business = Business::new(saved.name, &config)
+            .with_day(saved.day_num)
+            .with_ledger(saved.ledger)
+            .with_shops(saved.shops)
+            .with_scooter_parts(saved.scooter_parts)
+            .with_bank(saved.bank);
+
+        let weather = saved.weather.with_config(config.weather);
+
+        Self {
+            day_num: saved.day_num,
+            phase: if saved.game_over.is_some() { Phase::Over } else { saved.phase },
+            seed: saved.seed,
+            rng: saved.rng,
+            business,
+            weather,
+            difficulty: saved.difficulty,
+            demand: Arc::new(ElasticDemand::new(config.demand)),
+            config,
+            history: saved.history,
+            today: saved.today,
+            days_insolvent: saved.days_insolvent,
+            game_over: saved.game_over,
+        }
+    }
+
+    /// Swap in a different demand model. Games start with an `ElasticDemand`
+    /// built from the config; the model isn't saved, so set it again after loading.
+    pub fn with_demand_model(mut self, demand: Arc<dyn DemandModel>) -> Self
+    { self.demand = demand; self }
+
+    pub fn to_save_file(&self) -> SaveFile {
+        SaveFile::new(
+            self.day_num,
+            self.seed,
+            self.rng.clone(),
+            self.business.name(),
+            self.business.ledger().clone(),
+            self.business.shops_by_location().clone(),
+            self.business.scooter_parts(),
+            self.business.bank().clone(),
+            self.weather,
+            self.history.clone(),
+            self.days_insolvent,
+            self.game_over,
+            self.difficulty,
+            self.config.weather.climate,
+            self.phase,
+            self.today.clone(),
+        )
+    }
+
+    pub fn day(&self) -> u32 { self.day_num }
+    pub fn phase(&self) -> Phase { self.phase }
+    pub fn seed(&self) -> u64 { self.seed }
+    pub fn business(&self) -> &Business { &self.business }
+    pub fn weather(&self) -> &Weather { &self.weather }
+    pub fn config(&self) -> &GameConfig { &self.config }
+    pub fn difficulty(&self) -> Difficulty { self.difficulty }
+    pub fn climate(&self) -> &ClimateProfile { &self.config.weather.climate }
+    pub fn history(&self) -> &History { &self.history }
+    pub fn days_insolvent(&self) -> u32 { self.days_insolvent }
+    /// Why the game ended, once it has.
+    pub fn game_over(&self) -> Option<GameOver> { self.game_over }
+
+    /// Apply a single player decision, moving the game along when it ends a phase.
+    pub fn apply(&mut self, command: Command) -> Result<Outcome, GameError> {
+        let outcome = self.run(command)?;
+        match &outcome {
+            Outcome::Rented(receipts) => {
+                if let Command::Rent { prices, .. } = command {
+                    self.today = Some(DayRecord {
+                        day: self.day_num,
+                        prices,
+                        receipts: receipts.clone(),
+                        actions: Vec::new(),
+                        weather: self.weather.get_current(),
+                        temperature: self.weather.get_temperature(),
+                        season: self.weather.get_season(),
+                        forecast: self.weather.get_forecast(),
+                    });
+                }
+            },
+            Outcome::DayStarted { .. } => (),
+            Outcome::DayEnded { .. } => (),
+            _ => {
+                if let Some(today) = self.today.as_mut() { today.actions.push(command) }
+            },
+        }
+        Ok(outcome)
+    }
+
+    fn run(&mut self, command: Command) -> Result<Outcome, GameError> {
+        match (self.phase, command) {
+            (Phase::DayStart, Command::StartDay) => {
+                self.phase = Phase::Rental;
+                Ok(Outcome::DayStarted { day: self.day_num, weather: Box::new(self.weather) })
+            },
+            (Phase::Rental, Command::Rent { deploy, prices }) => {
+                // Rent on copies so a problem at one shop leaves the day untouched
+                let mut business = self.business.clone();
+                let mut rng = self.rng.clone();
+                let mut receipts = Vec::new();
+                for (at, num) in deploy.iter() {
+                    if !business.is_open(at) {
+                        if num > 0 { return Err(ManagementError::LocationClosed(at).into()) }
+                        continue;
+                    }
+                    receipts.push(business.rent_scooters(
+                        &mut rng,
+                        self.demand.as_ref(),
+                        at,
+                        num,
+                        prices.get(at),
+                        &self.weather,
+                    )?);
+                }
+                self.business = business;
+                self.rng = rng;
+                self.phase = Phase::Management;
+                Ok(Outcome::Rented(receipts))
+            },
+            (Phase::Management, Command::BuyScooters { at, num }) => {
+                let price = self.config.prices.scooter;
+                self.business.buy_scooters(at, num, price)?;
+                Ok(Outcome::BoughtScooters { at, num, cost: num as f32 * price })
+            },
+            (Phase::Management, Command::BuyParts(num)) => {
+                let price = self.config.prices.part;
+                self.business.buy_scooter_parts(num, price)?;
+                Ok(Outcome::BoughtParts { num, cost: num as f32 * price })
+            },
+            (Phase::Management, Command::SellScooters { at, num }) => {
+                let income = self.business.sell_working_scooters(at, num, self.scooter_resale_price())?;
+                Ok(Outcome::SoldScooters { at, num, income })
+            },
+            (Phase::Management, Command::RepairScooters { at, num }) => {
+                self.business.repair_scooters(at, num)?;
+                Ok(Outcome::RepairedScooters { at, num })
+            },
+            (Phase::Management, Command::BuyAdvertisements { at, num }) => {
+                let price = self.config.prices.advert;
+                self.business.buy_advertisements(at, num, price)?;
+                Ok(Outcome::BoughtAdvertisements { at, num, cost: num as f32 * price })
+            },
+            (Phase::Management, Command::OpenLocation(at)) => {
+                self.business.open_location(at)?;
+                Ok(Outcome::OpenedLocation { at, cost: self.config.locations.get(at).open_cost })
+            },
+            (Phase::Management, Command::TransferScooters { from, to, num }) => {
+                let price = self.config.prices.transfer;
+                self.business.transfer_scooters(from, to, num, price)?;
+                Ok(Outcome::TransferredScooters { from, to, num, cost: num as f32 * price })
+            },
+            (Phase::Management, Command::TakeLoan(amount)) => {
+                let loan = self.business.take_loan(amount)?;
+                Ok(Outcome::TookLoan { amount, daily_payment: loan.daily_payment, days: loan.payments_left })
+            },
+            (Phase::Management, Command::RepayLoans(amount)) => {
+                let amount = self.business.repay_loans(amount)?;
+                Ok(Outcome::RepaidLoans { amount })
+            },
+            (Phase::Management, Command::EndDay) => {
+                if let Some(mut today) = self.today.take() {
+                    today.forecast = self.weather.get_forecast();
+                    self.history.push(today);
+                }
+                let day = self.day_num;
+                let season = self.weather.get_season();
+                let season_start = self.season_start();
+                self.day_num += 1;
+                self.weather.new_day(&mut self.rng);
+                let season_ended = self.weather.get_season() != season;
+                self.business.new_day(season_ended);
+                let season_report = season_ended.then(|| self.report(season_start, day));
+                self.game_over = self.check_ending(day);
+                self.phase = if self.game_over.is_some() { Phase::Over } else { Phase::DayStart };
+                Ok(Outcome::DayEnded { day, season_report, game_over: self.game_over })
+            },
+            (phase, command) => Err(GameError::WrongPhase { phase, command }),
+        }
+    }
+
+    /// Called each night once the bills are paid, to see whether `day` was the last.
+    fn check_ending(&mut self, day: u32) -> Option<GameOver> {
+        let business = &self.business;
+        if business.net_worth(self.scooter_resale_price()) < 0.0 || business.past_overdraft() {
+            self.days_insolvent += 1;
+        } else {
+            self.days_insolvent = 0;
+        }
+        let no_scooters = business.working_scooters() + business.broken_scooters() == 0;
+        if self.days_insolvent >= self.config.ending.bankruptcy_days {
+            Some(GameOver::Bankrupt)
+        } else if no_scooters && business.spending_power() < self.config.prices.scooter {
+            Some(GameOver::OutOfScooters)
+        } else if self.config.ending.last_day.is_some_and(|last| day >= last) {
+            Some(GameOver::ReachedLastDay)
+        } else {
+            None
+        }
+    }
+
+    /// How the business did from `first_day` to `last_day`, including today so far.
+    pub fn report(&self, first_day: u32, last_day: u32) -> Report {
+        Report::new(self.business.ledger(), self.history.days().iter().chain(&self.today), first_day, last_day)
+    }
+
+    /// The first day of the current season, or today for a game loaded part way through one.
+    pub fn season_start(&self) -> u32 {
+        let season = self.weather.get_season();
+        self.history.days().iter().chain(&self.today).rev()
+            .take_while(|record| record.season == season)
+            .last()
+            .map_or(self.day_num, |record| record.day)
+    }
+
+    pub fn scooter_resale_price(&self) -> f32 {
+        self.config.prices.scooter * self.config.prices.resale_fraction
+    }
+}
+
+#[cfg(test)]
+mod game_tests {
+    use crate::climate::Climate;
+    use crate::config::GameConfig;
+    use crate::difficulty::Difficulty;
+    use crate::game::{Command, Game, GameError, GameOver, Outcome, Phase};
+    use crate::business::ManagementError;
+    use crate::location::{Location, Locations};
+    use crate::period::Periods;
+    use crate::weather::Seasons;
+
+    /// Put out the starting scooters downtown at one price all day.
+    fn rent(price: f32) -> Command {
+        Command::Rent { deploy: Locations { downtown: 10, ..Locations::all(0) }, prices: Locations::all(Periods::all(price)) }
+    }
+
+    fn play(game: &mut Game, commands: &[Command]) -> Vec<Outcome> {
+        commands.iter().map(|command| game.apply(*command).unwrap()).collect()
+    }
+
+    #[test]
+    fn commands_follow_the_day() {
+        let mut game = Game::new("Scoots".to_string(), 5, GameConfig::default());
+        assert_eq!(game.phase(), Phase::DayStart);
+        game.apply(Command::StartDay).unwrap();
+        assert_eq!(game.phase(), Phase::Rental);
+        game.apply(rent(15.0)).unwrap();
+        assert_eq!(game.phase(), Phase::Management);
+        assert_eq!(game.apply(Command::EndDay), Ok(Outcome::DayEnded { day: 1, season_report: None, game_over: None }));
+        assert_eq!(game.phase(), Phase::DayStart);
+        assert_eq!(game.day(), 2);
+    }
+
+    #[test]
+    fn wrong_phase_is_rejected() {
+        let mut game = Game::new("Scoots".to_string(), 5, GameConfig::default());
+        assert_eq!(
+            game.apply(Command::BuyParts(1)),
+            Err(GameError::WrongPhase { phase: Phase::DayStart, command: Command::BuyParts(1) })
+        );
+    }
+
+    #[test]
+    fn seed_and_decisions_replay_the_game() {
+        let commands = [
+            Command::StartDay, rent(12.0), Command::BuyParts(1), Command::EndDay,
+            Command::StartDay, rent(18.0), Command::EndDay,
+            Command::StartDay, rent(9.0), Command::EndDay,
+        ];
+        let mut first = Game::new("Scoots".to_string(), 1234, GameConfig::default());
+        let mut second = Game::new("Scoots".to_string(), 1234, GameConfig::default());
+        assert_eq!(play(&mut first, &commands), play(&mut second, &commands));
+        assert_eq!(first.business().cash(), second.business().cash());
+    }
+
+    #[test]
+    fn history_replays_the_game() {
+        let mut game = Game::new("Scoots".to_string(), 99, GameConfig::default());
+        play(&mut game, &[Command::StartDay, rent(14.0), Command::BuyParts(1), Command::EndDay]);
+        let downtown = game.business().rentable_scooters(Location::Downtown);
+        play(&mut game, &[
+            Command::StartDay,
+            Command::Rent { deploy: Locations { downtown, ..Locations::all(0) }, prices: Locations::all(Periods::all(16.0)) },
+            Command::RepairScooters { at: Location::Downtown, num: 0 },
+            Command::EndDay,
+        ]);
+        let history = game.history();
+        assert_eq!(history.days().len(), 2);
+        assert_eq!(history.days()[0].actions, vec![Command::BuyParts(1)]);
+        assert_eq!(history.days()[1].prices.downtown, Periods::all(16.0));
+
+        let mut replay = Game::new("Scoots".to_string(), 99, GameConfig::default());
+        play(&mut replay, &history.commands());
+        assert_eq!(replay.history(), game.history());
+        assert_eq!(replay.business().cash(), game.business().cash());
+    }
+
+    #[test]
+    fn a_game_saved_mid_day_carries_on_where_it_was() {
+        let mut game = Game::new("Scoots".to_string(), 99, GameConfig::default());
+        play(&mut game, &[Command::StartDay, rent(14.0), Command::BuyParts(1)]);
+        let mut loaded = Game::from_save_file(game.to_save_file(), GameConfig::default());
+        assert_eq!(loaded.phase(), Phase::Management);
+        assert_eq!(loaded.apply(rent(14.0)), Err(GameError::WrongPhase { phase: Phase::Management, command: rent(14.0) }));
+        play(&mut loaded, &[Command::EndDay]);
+        assert_eq!(loaded.history().days()[0].actions, vec![Command::BuyParts(1)]);
+
+        let mut replay = Game::new("Scoots".to_string(), 99, GameConfig::default());
+        play(&mut replay, &loaded.history().commands());
+        assert_eq!(replay.history(), loaded.history());
+        assert_eq!(replay.business().ledger(), loaded.business().ledger());
+    }
+
+    #[test]
+    fn renting_at_a_closed_shop_changes_nothing() {
+        let mut game = Game::new("Scoots".to_string(), 5, GameConfig::default());
+        game.apply(Command::StartDay).unwrap();
+        let before = game.business().cash();
+        let command = Command::Rent { deploy: Locations { downtown: 10, beach: 2, campus: 0 }, prices: Locations::all(Periods::all(12.0)) };
+        assert_eq!(game.apply(command), Err(GameError::Management(ManagementError::LocationClosed(Location::Beach))));
+        assert_eq!(game.phase(), Phase::Rental);
+        assert_eq!(game.business().cash(), before);
+        assert_eq!(game.business().shop(Location::Downtown).unwrap().fleet().scooters().iter().map(|s| s.mileage()).sum::<f32>(), 0.0);
+    }
+
+    #[test]
+    fn open_shops_rent_separately() {
+        let mut config = GameConfig::default();
+        config.starting.cash = 1000.0;
+        let mut game = Game::new("Scoots".to_string(), 5, config);
+        play(&mut game, &[
+            Command::StartDay, rent(15.0),
+            Command::OpenLocation(Location::Campus),
+            Command::TransferScooters { from: Location::Downtown, to: Location::Campus, num: 3 },
+            Command::EndDay, Command::StartDay,
+        ]);
+        let deploy = Locations { downtown: game.business().rentable_scooters(Location::Downtown), beach: 0, campus: 3 };
+        match game.apply(Command::Rent { deploy, prices: Locations::all(Periods::all(8.0)) }) {
+            Ok(Outcome::Rented(receipts)) => {
+                let at: Vec<Location> = receipts.iter().map(|receipt| receipt.location()).collect();
+                assert_eq!(at, vec![Location::Downtown, Location::Campus]);
+            },
+            other => panic!("Rent failed: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn a_report_arrives_when_the_season_ends() {
+        let mut config = GameConfig::default();
+        config.weather.climate.season_days = Seasons { spring: 2, summer: 2, fall: 2, winter: 2 };
+        let mut game = Game::new("Scoots".to_string(), 7, config);
+        let mut reports = Vec::new();
+        for _ in 0..6 {
+            let downtown = game.business().rentable_scooters(Location::Downtown);
+            play(&mut game, &[
+                Command::StartDay,
+                Command::Rent { deploy: Locations { downtown, ..Locations::all(0) }, prices: Locations::all(Periods::all(12.0)) },
+            ]);
+            if let Ok(Outcome::DayEnded { season_report: Some(report), .. }) = game.apply(Command::EndDay) {
+                reports.push(report);
+            }
+        }
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].first_day, reports[0].last_day + 1);
+        assert_eq!(reports[1].last_day - reports[1].first_day + 1, 2);
+        assert_eq!(reports[1].rented, game.history().days()[reports[1].first_day as usize - 1..reports[1].last_day as usize]
+            .iter().map(|record| record.rented()).sum::<u32>());
+    }
+
+    /// A day with every scooter kept in.
+    fn idle_day(game: &mut Game) -> Outcome {
+        game.apply(Command::StartDay).unwrap();
+        game.apply(Command::Rent { deploy: Locations::all(0), prices: Locations::all(Periods::all(10.0)) }).unwrap();
+        game.apply(Command::EndDay).unwrap()
+    }
+
+    #[test]
+    fn the_game_ends_on_the_last_day() {
+        let mut config = GameConfig::default();
+        config.ending.last_day = Some(2);
+        let mut game = Game::new("Scoots".to_string(), 5, config);
+        assert!(matches!(idle_day(&mut game), Outcome::DayEnded { game_over: None, .. }));
+        assert!(matches!(idle_day(&mut game), Outcome::DayEnded { game_over: Some(GameOver::ReachedLastDay), .. }));
+        assert_eq!(game.phase(), Phase::Over);
+        assert_eq!(
+            game.apply(Command::StartDay),
+            Err(GameError::WrongPhase { phase: Phase::Over, command: Command::StartDay })
+        );
+    }
+
+    #[test]
+    fn insolvent_businesses_go_bankrupt() {
+        let mut config = GameConfig::default();
+        config.starting.cash = 0.0;
+        config.prices.resale_fraction = 0.0;
+        config.ending.bankruptcy_days = 2;
+        let mut game = Game::new("Scoots".to_string(), 5, config);
+        assert!(matches!(idle_day(&mut game), Outcome::DayEnded { game_over: None, .. }));
+        assert_eq!(game.days_insolvent(), 1);
+        assert!(matches!(idle_day(&mut game), Outcome::DayEnded { game_over: Some(GameOver::Bankrupt), .. }));
+        assert_eq!(game.game_over(), Some(GameOver::Bankrupt));
+    }
+
+    #[test]
+    fn no_scooters_and_no_money_ends_the_game() {
+        let mut config = GameConfig::default();
+        config.starting.cash = 0.0;
+        config.starting.scooters = 0;
+        let mut game = Game::new("Scoots".to_string(), 5, config);
+        assert!(matches!(idle_day(&mut game), Outcome::DayEnded { game_over: Some(GameOver::OutOfScooters), .. }));
+    }
+
+    #[test]
+    fn finished_games_stay_finished() {
+        let mut config = GameConfig::default();
+        config.ending.last_day = Some(1);
+        let mut game = Game::new("Scoots".to_string(), 5, config.clone());
+        idle_day(&mut game);
+        let save = game.to_save_file();
+        assert!(save.summary().finished);
+        let loaded = Game::from_save_file(save, config);
+        assert_eq!(loaded.phase(), Phase::Over);
+        assert_eq!(loaded.game_over(), Some(GameOver::ReachedLastDay));
+    }
+
+    #[test]
+    fn difficulty_is_kept_with_the_game() {
+        let config = GameConfig::default();
+        let hard = Game::new_at_difficulty("Scoots".to_string(), 5, config.clone(), Difficulty::Hard);
+        let normal = Game::new("Scoots".to_string(), 5, config.clone());
+        assert!(hard.business().cash() < normal.business().cash());
+        assert!(hard.config().prices.scooter > normal.config().prices.scooter);
+
+        let loaded = Game::from_save_file(hard.to_save_file(), config);
+        assert_eq!(loaded.difficulty(), Difficulty::Hard);
+        assert_eq!(loaded.config(), hard.config());
+    }
+
+    #[test]
+    fn climate_is_kept_with_the_game() {
+        let mut config = GameConfig::default();
+        config.weather.climate = Climate::Nordic.profile();
+        let game = Game::new("Scoots".to_string(), 5, config);
+
+        let loaded = Game::from_save_file(game.to_save_file(), GameConfig::default());
+        assert_eq!(loaded.climate().climate, Climate::Nordic);
+        assert_eq!(loaded.weather(), game.weather());
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use crate::business::Receipt;
+use crate::game::Command;
+use crate::location::Locations;
+use crate::period::Periods;
+use crate::weather::{Season, Temperature, WeatherType};
+
+/// Everything that happened on one finished day.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DayRecord {
+    pub day: u32,
+    /// What each shop charged, including any that weren't open.
+    pub prices: Locations<Periods<f32>>,
+    /// One receipt for each shop that was open.
+    pub receipts: Vec<Receipt>,
+    /// Management commands that went through, in the order they were given.
+    pub actions: Vec<Command>,
+    // weather actually experienced
+    pub weather: WeatherType,
+    pub temperature: Temperature,
+    pub season: Season,
+    /// The forecast for the next day, as the player saw it at the end of this one.
+    pub forecast: WeatherType,
+}
+
+impl DayRecord {
+    /// Scooters put out at each location.
+    pub fn deployed(&self) -> Locations<u32> {
+        let mut deployed = Locations::all(0);
+        for receipt in &self.receipts {
+            *deployed.get_mut(receipt.location()) = receipt.deployed();
+        }
+        deployed
+    }
+
+    pub fn rented(&self) -> u32 { self.receipts.iter().map(|receipt| receipt.rented()).sum() }
+    pub fn profit(&self) -> f32 { self.receipts.iter().map(|receipt| receipt.profit()).sum() }
+    pub fn broken_scooters(&self) -> u32 { self.receipts.iter().map(|receipt| receipt.broken_scooters()).sum() }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct History {
+    days: Vec<DayRecord>,
+}
+
+impl History {
+    pub fn days(&self) -> &[DayRecord] { &self.days }
+    pub fn last(&self) -> Option<&DayRecord> { self.days.last() }
+
+    pub fn push(&mut self, record: DayRecord) {
+        self.days.push(record);
+    }
+
+    /// The player's decisions, as commands which replay the game from its seed.
+    pub fn commands(&self) -> Vec<Command> {
+        let mut commands = Vec::new();
+        for record in &self.days {
+            commands.push(Command::StartDay);
+            commands.push(Command::Rent { deploy: record.deployed(), prices: record.prices });
+            commands.extend(record.actions.iter().copied());
+            commands.push(Command::EndDay);
+        }
+        commands
+    }
+}
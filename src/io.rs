@@ -1,83 +1,470 @@
-use std::fs::{File, OpenOptions};
-use std::io::{Read, Write};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Read, Write};
+use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
+use crate::bank::Bank;
+use crate::climate::ClimateProfile;
+use crate::difficulty::Difficulty;
+use crate::game::{GameOver, Phase};
+use crate::history::{DayRecord, History};
+use crate::ledger::Ledger;
+use crate::location::{Locations, Shop};
+use crate::migrations;
+use crate::rng::GameRng;
+use crate::weather::Weather;
+
+/// Layout version written into every new save file.
+/// Bump this, and add a step to `migrations`, whenever `SaveFile` changes.
+pub const SAVE_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    UnsupportedVersion(u32),
+    InvalidValue(String),
+    InvalidSlotName(String),
+    SlotExists(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Io(err) => write!(f, "couldn't read or write the save file: {}", err),
+            SaveError::Parse(err) => write!(f, "the save file is damaged: {}", err),
+            SaveError::UnsupportedVersion(version) => write!(
+                f, "the save file is version {}, but this game only understands up to version {}",
+                version, SAVE_VERSION),
+            SaveError::InvalidValue(problem) => write!(f, "the save file is damaged: {}", problem),
+            SaveError::InvalidSlotName(slot) => write!(
+                f, "\"{}\" can't be used as a save name; use letters, numbers, spaces, - or _", slot),
+            SaveError::SlotExists(slot) => write!(f, "there is already a save called \"{}\"", slot),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<std::io::Error> for SaveError {
+    fn from(err: std::io::Error) -> Self { SaveError::Io(err) }
+}
+
+impl From<ron::error::SpannedError> for SaveError {
+    fn from(err: ron::error::SpannedError) -> Self { SaveError::Parse(err) }
+}
 
 #[derive(Serialize, Deserialize, Debug)]
-pub(crate) struct SaveFile {
+pub struct SaveFile {
     // general
-    pub(crate) day_num: u32,
+    pub version: u32,
+    pub day_num: u32,
+    pub seed: u64,
+    pub rng: GameRng,
     // business
-    pub(crate) name: String,
-    pub(crate) cash: f32,
-    pub(crate) scooters_working: u32,
-    pub(crate) scooters_broken: u32,
-    pub(crate) scooter_parts: u32,
-    pub(crate) num_advertisements: u32,
+    pub name: String,
+    /// Every transaction the business has made, which cash is worked out from.
+    pub ledger: Ledger,
+    pub shops: Locations<Option<Shop>>,
+    pub scooter_parts: u32,
+    /// Loans still being paid back.
+    pub bank: Bank,
     // weather
-    pub(crate) current: String,
-    pub(crate) forecast: String,
-    pub(crate) temperature: String,
-    pub(crate) season: String,
-    pub(crate) days_of_season: u8,
+    pub weather: Weather,
+    // history
+    pub history: History,
+    // ending
+    /// Nights in a row the business has ended insolvent.
+    pub days_insolvent: u32,
+    /// Set once the game has ended; a finished save can't be played on.
+    pub game_over: Option<GameOver>,
+    /// Chosen when the game started; applied to the config whenever it's loaded.
+    pub difficulty: Difficulty,
+    /// Where the business is, kept whole so the weather carries on the same
+    /// even if the climate files change.
+    pub climate: ClimateProfile,
+    // the day in progress
+    /// Where in the day the game was saved, so a day already rented isn't rented again.
+    pub phase: Phase,
+    /// The record of the day so far, once its scooters have been rented out.
+    pub today: Option<DayRecord>,
 }
 
 impl SaveFile {
 
-    pub(crate) fn to_ron(&self) -> String {
+    pub fn to_ron(&self) -> String {
         // serialize self elements into string for saving
         // TODO: Handle errors
         ron::ser::to_string_pretty(&self, ron::ser::PrettyConfig::default()).unwrap()
     }
 
-    pub(crate) fn from_ron(ron_text: &str) -> Self {
-        // deserialize loaded string into self elements
-        ron::from_str(ron_text).unwrap()
+    pub fn from_ron(ron_text: &str) -> Result<Self, SaveError> {
+        // deserialize loaded string into self elements, upgrading older layouts
+        let save = migrations::upgrade(ron_text)?;
+        if !save.difficulty.is_valid() {
+            return Err(SaveError::InvalidValue(
+                "custom difficulty settings must not be negative".to_string()));
+        }
+        Ok(save)
     }
 
-    pub(crate) fn load_save_file(file_path: &str) -> Result<SaveFile, std::io::Error> {
+    pub fn load_save_file(file_path: &str) -> Result<SaveFile, SaveError> {
         let mut content = String::new();
         let mut file = File::open(file_path)?;
         file.read_to_string(&mut content)?;
-        Ok(SaveFile::from_ron(content.as_str()))
+        SaveFile::from_ron(content.as_str())
+    }
+
+    pub fn summary(&self) -> SaveSummary {
+        SaveSummary {
+            name: self.name.clone(),
+            day_num: self.day_num,
+            cash: self.ledger.cash(),
+            finished: self.game_over.is_some(),
+        }
     }
 
-    pub(crate) fn write_save_file(&self, file_path: &str) -> Result<(), std::io::Error> {
+    /// Writes to a temporary file next to `file_path` and renames it into place,
+    /// so a crash part way through never leaves a half written save behind.
+    pub fn write_save_file(&self, file_path: &str) -> Result<(), std::io::Error> {
+        let tmp_path = format!("{}.tmp", file_path);
         let mut file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
-            .open(file_path)?;
+            .open(&tmp_path)?;
         file.write_all(self.to_ron().as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, file_path)?;
         Ok(())
     }
 
-    pub(crate) fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
         day_num: u32,
+        seed: u64,
+        rng: GameRng,
         name: String,
-        cash: f32,
-        scooters_working: u32,
-        scooters_broken: u32,
+        ledger: Ledger,
+        shops: Locations<Option<Shop>>,
         scooter_parts: u32,
-        num_advertisements: u32,
-        current: String,
-        forecast: String,
-        temperature: String,
-        season: String,
-        days_of_season: u8
+        bank: Bank,
+        weather: Weather,
+        history: History,
+        days_insolvent: u32,
+        game_over: Option<GameOver>,
+        difficulty: Difficulty,
+        climate: ClimateProfile,
+        phase: Phase,
+        today: Option<DayRecord>,
     ) -> Self {
         SaveFile {
+            version: SAVE_VERSION,
             day_num,
+            seed,
+            rng,
             name,
-            cash,
-            scooters_working,
-            scooters_broken,
+            ledger,
+            shops,
             scooter_parts,
-            num_advertisements,
-            current,
-            forecast,
-            temperature,
-            season,
-            days_of_season
+            bank,
+            weather,
+            history,
+            days_insolvent,
+            game_over,
+            difficulty,
+            climate,
+            phase,
+            today,
+        }
+    }
+}
+
+/// The headline details of a save, for listing saves without loading them into a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSummary {
+    pub name: String,
+    pub day_num: u32,
+    pub cash: f32,
+    /// Whether the game in the save is over.
+    pub finished: bool,
+}
+
+#[derive(Debug)]
+pub struct SlotInfo {
+    pub slot: String,
+    pub summary: Result<SaveSummary, SaveError>,
+}
+
+/// Named save games, each kept as `<slot>.ron` in one directory.
+#[derive(Debug, Clone)]
+pub struct SaveSlots {
+    dir: PathBuf,
+}
+
+impl SaveSlots {
+    const EXTENSION: &'static str = "ron";
+    /// How many earlier saves are kept for each slot.
+    pub const BACKUPS: usize = 3;
+
+    pub fn new(dir: &str) -> Self {
+        Self { dir: PathBuf::from(dir) }
+    }
+
+    pub fn path(&self, slot: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", slot, Self::EXTENSION))
+    }
+
+    /// Backups are numbered from 1, the most recent.
+    pub fn backup_path(&self, slot: &str, num: usize) -> PathBuf {
+        self.dir.join(format!("{}.{}.bak{}", slot, Self::EXTENSION, num))
+    }
+
+    pub fn exists(&self, slot: &str) -> bool {
+        self.path(slot).is_file()
+    }
+
+    /// Every save in the directory, sorted by slot name. Saves that can't be
+    /// read are still listed, along with what is wrong with them.
+    pub fn list(&self) -> Result<Vec<SlotInfo>, SaveError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(Self::EXTENSION) { continue }
+            if let Some(slot) = path.file_stem().and_then(|stem| stem.to_str()) {
+                let summary = self.load(slot).map(|save| save.summary());
+                slots.push(SlotInfo { slot: slot.to_string(), summary });
+            }
+        }
+        slots.sort_by(|a, b| a.slot.cmp(&b.slot));
+        Ok(slots)
+    }
+
+    pub fn load(&self, slot: &str) -> Result<SaveFile, SaveError> {
+        Self::check_name(slot)?;
+        SaveFile::load_save_file(&self.path(slot).to_string_lossy())
+    }
+
+    /// Write a save into a slot, replacing whatever was there.
+    /// The save being replaced joins the slot's backups, as long as it still loads.
+    pub fn save(&self, slot: &str, save: &SaveFile) -> Result<(), SaveError> {
+        Self::check_name(slot)?;
+        fs::create_dir_all(&self.dir)?;
+        if self.load(slot).is_ok() {
+            self.rotate_backups(slot)?;
+        }
+        save.write_save_file(&self.path(slot).to_string_lossy())?;
+        Ok(())
+    }
+
+    /// The backups kept for a slot, most recent first, with what's in them.
+    pub fn backups(&self, slot: &str) -> Vec<(usize, Result<SaveSummary, SaveError>)> {
+        (1..=Self::BACKUPS)
+            .filter(|num| self.backup_path(slot, *num).is_file())
+            .map(|num| (num, self.load_backup(slot, num).map(|save| save.summary())))
+            .collect()
+    }
+
+    pub fn load_backup(&self, slot: &str, num: usize) -> Result<SaveFile, SaveError> {
+        Self::check_name(slot)?;
+        SaveFile::load_save_file(&self.backup_path(slot, num).to_string_lossy())
+    }
+
+    fn rotate_backups(&self, slot: &str) -> Result<(), SaveError> {
+        for num in (1..Self::BACKUPS).rev() {
+            let from = self.backup_path(slot, num);
+            if from.is_file() {
+                fs::rename(from, self.backup_path(slot, num + 1))?;
+            }
+        }
+        fs::copy(self.path(slot), self.backup_path(slot, 1))?;
+        Ok(())
+    }
+
+    /// Write a save into a new slot, refusing to replace an existing one.
+    pub fn create(&self, slot: &str, save: &SaveFile) -> Result<(), SaveError> {
+        Self::check_name(slot)?;
+        if self.exists(slot) { return Err(SaveError::SlotExists(slot.to_string())) }
+        self.save(slot, save)
+    }
+
+    pub fn duplicate(&self, from: &str, to: &str) -> Result<(), SaveError> {
+        let save = self.load(from)?;
+        self.create(to, &save)
+    }
+
+    /// Delete a save along with its backups.
+    pub fn delete(&self, slot: &str) -> Result<(), SaveError> {
+        Self::check_name(slot)?;
+        fs::remove_file(self.path(slot))?;
+        for num in 1..=Self::BACKUPS {
+            let _ = fs::remove_file(self.backup_path(slot, num));
+        }
+        Ok(())
+    }
+
+    fn check_name(slot: &str) -> Result<(), SaveError> {
+        let allowed = |c: char| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_';
+        if slot.trim().is_empty() || slot.trim() != slot || !slot.chars().all(allowed) {
+            return Err(SaveError::InvalidSlotName(slot.to_string()))
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod save_file_tests {
+    use rand::Rng;
+    use crate::bank::Bank;
+    use crate::climate::Climate;
+    use crate::difficulty::{Difficulty, DifficultySettings};
+    use crate::fleet::Fleet;
+    use crate::game::Phase;
+    use crate::history::History;
+    use crate::io::{SaveError, SaveFile};
+    use crate::ledger::Ledger;
+    use crate::location::{Location, Locations, Shop};
+    use crate::rng::seeded;
+    use crate::weather::Weather;
+
+    fn shops() -> Locations<Option<Shop>> {
+        let mut shops = Locations::default();
+        *shops.get_mut(Location::Downtown) = Some(Shop::new(Fleet::new(10, 1)));
+        shops
+    }
+
+    #[test]
+    fn rng_survives_round_trip() {
+        let mut rng = seeded(99);
+        let _: u32 = rng.gen();
+        let save = SaveFile::new(3, 99, rng.clone(), "Scoots".to_string(), Ledger::opening(1, 100.0), shops(), 0, Bank::default(),
+            Weather::default(), History::default(), 0, None, Difficulty::Hard, Climate::Nordic.profile(), Phase::DayStart,
+            None);
+        let mut loaded = SaveFile::from_ron(save.to_ron().as_str()).unwrap();
+        assert_eq!(loaded.seed, 99);
+        assert_eq!(loaded.rng.gen::<u64>(), rng.gen::<u64>());
+        assert_eq!(loaded.climate, Climate::Nordic.profile());
+    }
+
+    #[test]
+    fn negative_custom_difficulty_is_rejected() {
+        let difficulty = Difficulty::Custom(DifficultySettings { demand: -1.0, ..DifficultySettings::NORMAL });
+        let save = SaveFile::new(3, 99, seeded(99), "Scoots".to_string(), Ledger::opening(1, 100.0), shops(), 0,
+            Bank::default(), Weather::default(), History::default(), 0, None, difficulty, Climate::Temperate.profile(),
+            Phase::DayStart, None);
+        match SaveFile::from_ron(save.to_ron().as_str()) {
+            Err(SaveError::InvalidValue(problem)) => assert!(problem.contains("difficulty")),
+            other => panic!("Expected invalid value, got {:?}", other),
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod save_slots_tests {
+    use crate::bank::Bank;
+    use crate::climate::Climate;
+    use crate::difficulty::Difficulty;
+    use crate::fleet::Fleet;
+    use crate::game::Phase;
+    use crate::history::History;
+    use crate::io::{SaveError, SaveFile, SaveSlots};
+    use crate::ledger::Ledger;
+    use crate::location::{Location, Locations, Shop};
+    use crate::rng::seeded;
+    use crate::weather::Weather;
+
+    fn shops() -> Locations<Option<Shop>> {
+        let mut shops = Locations::default();
+        *shops.get_mut(Location::Downtown) = Some(Shop::new(Fleet::new(10, 1)));
+        shops
+    }
+
+    fn slots(test: &str) -> SaveSlots {
+        let dir = std::env::temp_dir().join(format!("scooter_slots_{}_{}", test, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        SaveSlots::new(&dir.to_string_lossy())
+    }
+
+    fn save(name: &str, day_num: u32) -> SaveFile {
+        SaveFile::new(day_num, 1, seeded(1), name.to_string(), Ledger::opening(1, 150.0), shops(), 0, Bank::default(),
+            Weather::default(), History::default(), 0, None, Difficulty::Normal, Climate::Temperate.profile(), Phase::DayStart,
+            None)
+    }
+
+    #[test]
+    fn create_list_and_load() {
+        let slots = slots("create");
+        assert!(slots.list().unwrap().is_empty());
+        slots.create("zoom", &save("Zoom", 4)).unwrap();
+        slots.create("alpha", &save("Alpha", 2)).unwrap();
+        let listed = slots.list().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].slot, "alpha");
+        assert_eq!(listed[1].summary.as_ref().unwrap().day_num, 4);
+        assert_eq!(slots.load("zoom").unwrap().name, "Zoom");
+    }
+
+    #[test]
+    fn create_does_not_overwrite() {
+        let slots = slots("overwrite");
+        slots.create("zoom", &save("Zoom", 4)).unwrap();
+        assert!(matches!(slots.create("zoom", &save("Other", 1)), Err(SaveError::SlotExists(_))));
+    }
+
+    #[test]
+    fn duplicate_and_delete() {
+        let slots = slots("duplicate");
+        slots.create("zoom", &save("Zoom", 4)).unwrap();
+        slots.duplicate("zoom", "zoom copy").unwrap();
+        slots.delete("zoom").unwrap();
+        assert!(!slots.exists("zoom"));
+        assert_eq!(slots.load("zoom copy").unwrap().day_num, 4);
+    }
+
+    #[test]
+    fn saves_are_written_whole() {
+        let slots = slots("atomic");
+        slots.save("zoom", &save("Zoom", 4)).unwrap();
+        let tmp = format!("{}.tmp", slots.path("zoom").to_string_lossy());
+        assert!(!std::path::Path::new(&tmp).exists());
+        assert_eq!(slots.load("zoom").unwrap().day_num, 4);
+    }
+
+    #[test]
+    fn backups_roll() {
+        let slots = slots("backups");
+        for day in 1..=5 {
+            slots.save("zoom", &save("Zoom", day)).unwrap();
+        }
+        let backups = slots.backups("zoom");
+        assert_eq!(backups.len(), SaveSlots::BACKUPS);
+        assert_eq!(backups[0].1.as_ref().unwrap().day_num, 4);
+        assert_eq!(backups[2].1.as_ref().unwrap().day_num, 2);
+        assert_eq!(slots.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn damaged_save_can_be_recovered_from_backup() {
+        let slots = slots("recover");
+        slots.save("zoom", &save("Zoom", 1)).unwrap();
+        slots.save("zoom", &save("Zoom", 2)).unwrap();
+        std::fs::write(slots.path("zoom"), "(version: 1, day_num: ").unwrap();
+        assert!(matches!(slots.load("zoom"), Err(SaveError::Parse(_))));
+        assert_eq!(slots.load_backup("zoom", 1).unwrap().day_num, 1);
+        // A damaged save is not kept as a backup
+        slots.save("zoom", &save("Zoom", 3)).unwrap();
+        assert_eq!(slots.load_backup("zoom", 1).unwrap().day_num, 1);
+    }
+
+    #[test]
+    fn bad_names_are_rejected() {
+        let slots = slots("names");
+        assert!(matches!(slots.create("../escape", &save("Zoom", 1)), Err(SaveError::InvalidSlotName(_))));
+        assert!(matches!(slots.create("", &save("Zoom", 1)), Err(SaveError::InvalidSlotName(_))));
+    }
+}
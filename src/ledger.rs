@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+
+/// The accounts money moves between. Every transaction moves money between
+/// `Cash` and one other account, so all the balances together always add up to zero.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Account {
+    Cash,
+    /// What the owner put into the business.
+    Equity,
+    /// Money earned from renting scooters out.
+    Revenue,
+    /// Money spent on scooters, less what was got back selling them.
+    Fleet,
+    /// Money spent on repair parts.
+    Inventory,
+    /// Money spent keeping the business running.
+    Expenses,
+    /// Money owed to the bank.
+    Loans,
+}
+
+impl Account {
+    pub const ALL: [Account; 7] = [
+        Account::Cash, Account::Equity, Account::Revenue, Account::Fleet, Account::Inventory, Account::Expenses,
+        Account::Loans,
+    ];
+}
+
+/// What a transaction was for.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// The cash the business started with.
+    Opening,
+    Rentals,
+    ScooterPurchases,
+    ScooterSales,
+    Parts,
+    Advertising,
+    ShopOpenings,
+    Transfers,
+    Rent,
+    Storage,
+    Wages,
+    Insurance,
+    Borrowing,
+    LoanRepayments,
+    Interest,
+    OverdraftFees,
+}
+
+impl Category {
+    pub const ALL: [Category; 16] = [
+        Category::Opening, Category::Rentals, Category::ScooterPurchases, Category::ScooterSales, Category::Parts,
+        Category::Advertising, Category::ShopOpenings, Category::Transfers, Category::Rent, Category::Storage,
+        Category::Wages, Category::Insurance, Category::Borrowing, Category::LoanRepayments, Category::Interest,
+        Category::OverdraftFees,
+    ];
+
+    /// The account on the other side of cash for this kind of transaction.
+    pub fn counterpart(&self) -> Account {
+        match self {
+            Category::Opening => Account::Equity,
+            Category::Rentals => Account::Revenue,
+            Category::ScooterPurchases | Category::ScooterSales => Account::Fleet,
+            Category::Parts => Account::Inventory,
+            Category::Advertising | Category::ShopOpenings | Category::Transfers | Category::Rent
+                | Category::Storage | Category::Wages | Category::Insurance | Category::Interest
+                | Category::OverdraftFees => Account::Expenses,
+            Category::Borrowing | Category::LoanRepayments => Account::Loans,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Category::Opening => "starting cash".to_string(),
+            Category::Rentals => "rentals".to_string(),
+            Category::ScooterPurchases => "scooters bought".to_string(),
+            Category::ScooterSales => "scooters sold".to_string(),
+            Category::Parts => "parts".to_string(),
+            Category::Advertising => "advertising".to_string(),
+            Category::ShopOpenings => "opening shops".to_string(),
+            Category::Transfers => "moving scooters".to_string(),
+            Category::Rent => "rent".to_string(),
+            Category::Storage => "storage".to_string(),
+            Category::Wages => "wages".to_string(),
+            Category::Insurance => "insurance".to_string(),
+            Category::Borrowing => "loans taken".to_string(),
+            Category::LoanRepayments => "loans repaid".to_string(),
+            Category::Interest => "interest".to_string(),
+            Category::OverdraftFees => "overdraft fees".to_string(),
+        }
+    }
+}
+
+/// One movement of money. `amount` is what it did to cash, positive when money
+/// came in; the `counterpart` account moves by the same amount the other way.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+pub struct Transaction {
+    pub day: u32,
+    pub category: Category,
+    pub amount: f32,
+    pub counterpart: Account,
+}
+
+impl Transaction {
+    pub fn new(day: u32, category: Category, amount: f32) -> Self {
+        Self { day, category, amount, counterpart: category.counterpart() }
+    }
+}
+
+/// Every transaction the business has made, oldest first.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
+pub struct Ledger {
+    transactions: Vec<Transaction>,
+}
+
+impl Ledger {
+    /// A ledger whose only entry is `cash` put in by the owner on `day`.
+    pub fn opening(day: u32, cash: f32) -> Self {
+        let mut ledger = Self::default();
+        ledger.record(day, Category::Opening, cash);
+        ledger
+    }
+
+    pub fn record(&mut self, day: u32, category: Category, amount: f32) {
+        self.transactions.push(Transaction::new(day, category, amount));
+    }
+
+    pub fn transactions(&self) -> &[Transaction] { &self.transactions }
+
+    /// The transactions made on `day`.
+    pub fn on_day(&self, day: u32) -> impl Iterator<Item = &Transaction> {
+        self.transactions.iter().filter(move |transaction| transaction.day == day)
+    }
+
+    pub fn cash(&self) -> f32 { self.balance(Account::Cash) }
+
+    /// How much has gone into `account`, less what has come out.
+    pub fn balance(&self, account: Account) -> f32 {
+        self.transactions.iter()
+            .map(|transaction| match account {
+                Account::Cash => transaction.amount,
+                _ if transaction.counterpart == account => -transaction.amount,
+                _ => 0.0,
+            })
+            .sum()
+    }
+
+    /// The net effect on cash of every transaction in `category`.
+    pub fn total(&self, category: Category) -> f32 {
+        self.transactions.iter()
+            .filter(|transaction| transaction.category == category)
+            .map(|transaction| transaction.amount)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod ledger_tests {
+    use crate::ledger::{Account, Category, Ledger};
+
+    fn ledger() -> Ledger {
+        let mut ledger = Ledger::opening(1, 100.0);
+        ledger.record(1, Category::Rentals, 60.0);
+        ledger.record(1, Category::ScooterPurchases, -80.0);
+        ledger.record(2, Category::ScooterSales, 20.0);
+        ledger.record(2, Category::Rent, -10.0);
+        ledger
+    }
+
+    #[test]
+    fn cash_is_the_sum_of_every_transaction() {
+        assert_eq!(ledger().cash(), 90.0);
+        assert_eq!(ledger().on_day(2).count(), 2);
+    }
+
+    #[test]
+    fn the_books_balance() {
+        let ledger = ledger();
+        let total: f32 = Account::ALL.iter().map(|account| ledger.balance(*account)).sum();
+        assert_eq!(total, 0.0);
+        assert_eq!(ledger.balance(Account::Fleet), 60.0);
+        assert_eq!(ledger.balance(Account::Revenue), -60.0);
+    }
+
+    #[test]
+    fn totals_by_category() {
+        let ledger = ledger();
+        assert_eq!(ledger.total(Category::Rentals), 60.0);
+        assert_eq!(ledger.total(Category::Advertising), 0.0);
+    }
+}
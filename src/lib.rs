@@ -0,0 +1,17 @@
+pub mod bank;
+pub mod business;
+pub mod climate;
+pub mod config;
+pub mod demand;
+pub mod difficulty;
+pub mod fleet;
+pub mod game;
+pub mod history;
+pub mod io;
+pub mod ledger;
+pub mod location;
+mod migrations;
+pub mod period;
+pub mod report;
+pub mod rng;
+pub mod weather;
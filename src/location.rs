@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use crate::fleet::Fleet;
+
+/// The places the business can open a shop.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Location {
+    Downtown,
+    Beach,
+    Campus,
+}
+
+impl Location {
+    /// Every location, in the order they're listed to the player.
+    pub const ALL: [Location; 3] = [Location::Downtown, Location::Beach, Location::Campus];
+
+    pub fn describe(&self) -> String {
+        match self {
+            Location::Downtown => "downtown".to_string(),
+            Location::Beach => "beach".to_string(),
+            Location::Campus => "campus".to_string(),
+        }
+    }
+}
+
+/// One value for each `Location`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Locations<T> {
+    pub downtown: T,
+    pub beach: T,
+    pub campus: T,
+}
+
+impl<T: Copy> Locations<T> {
+    /// The same value everywhere.
+    pub fn all(val: T) -> Self {
+        Self { downtown: val, beach: val, campus: val }
+    }
+
+    pub fn get(&self, location: Location) -> T {
+        match location {
+            Location::Downtown => self.downtown,
+            Location::Beach => self.beach,
+            Location::Campus => self.campus,
+        }
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Location, T) -> U) -> Locations<U> {
+        Locations {
+            downtown: f(Location::Downtown, self.downtown),
+            beach: f(Location::Beach, self.beach),
+            campus: f(Location::Campus, self.campus),
+        }
+    }
+
+    /// Each location with its value, in the order of `Location::ALL`.
+    pub fn iter(&self) -> impl Iterator<Item = (Location, T)> {
+        [
+            (Location::Downtown, self.downtown),
+            (Location::Beach, self.beach),
+            (Location::Campus, self.campus),
+        ].into_iter()
+    }
+}
+
+impl<T> Locations<T> {
+    pub fn get_ref(&self, location: Location) -> &T {
+        match location {
+            Location::Downtown => &self.downtown,
+            Location::Beach => &self.beach,
+            Location::Campus => &self.campus,
+        }
+    }
+
+    pub fn get_mut(&mut self, location: Location) -> &mut T {
+        match location {
+            Location::Downtown => &mut self.downtown,
+            Location::Beach => &mut self.beach,
+            Location::Campus => &mut self.campus,
+        }
+    }
+}
+
+/// What it costs to run a shop at a location.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LocationConfig {
+    /// Paid once, when the shop opens.
+    pub open_cost: f32,
+    /// Paid at the end of every day the shop is open.
+    pub rent: f32,
+}
+
+impl Default for Locations<LocationConfig> {
+    fn default() -> Self {
+        Self {
+            downtown: LocationConfig { open_cost: 400.0, rent: 10.0 },
+            beach: LocationConfig { open_cost: 300.0, rent: 8.0 },
+            campus: LocationConfig { open_cost: 200.0, rent: 5.0 },
+        }
+    }
+}
+
+/// A shop open at one location, with its own scooters and advertising.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Shop {
+    fleet: Fleet,
+    num_advertisements: u32,
+}
+
+impl Shop {
+    pub fn new(fleet: Fleet) -> Self {
+        Self { fleet, num_advertisements: 0 }
+    }
+
+    pub fn with_num_adverts(mut self, num: u32) -> Self { self.num_advertisements = num; self }
+
+    pub fn fleet(&self) -> &Fleet { &self.fleet }
+    pub fn fleet_mut(&mut self) -> &mut Fleet { &mut self.fleet }
+    pub fn advertisements(&self) -> u32 { self.num_advertisements }
+    pub fn set_advertisements(&mut self, num: u32) { self.num_advertisements = num; }
+}
+
+#[cfg(test)]
+mod location_tests {
+    use crate::location::{Location, Locations, Shop};
+
+    #[test]
+    fn locations_are_listed_in_order() {
+        let order: Vec<Location> = Locations::all(0).iter().map(|(location, _)| location).collect();
+        assert_eq!(order, Location::ALL);
+    }
+
+    #[test]
+    fn shops_round_trip_through_ron() {
+        let shops = Locations { downtown: Some(Shop::default().with_num_adverts(2)), beach: None, campus: None };
+        let text = ron::to_string(&shops).unwrap();
+        assert!(text.contains("beach:None"));
+        assert_eq!(ron::from_str::<Locations<Option<Shop>>>(&text).unwrap(), shops);
+    }
+}
@@ -1,141 +1,92 @@
-
-mod business;
-mod weather;
 mod cli;
-mod io;
 
 use std::string::ToString;
-use crate::business::Business;
-use crate::weather::{ForecastTime, Weather};
+use scooter_rentals::config::GameConfig;
+use scooter_rentals::business::ManagementError;
+use scooter_rentals::climate::{Climate, ClimateProfile};
+use scooter_rentals::difficulty::{Difficulty, DifficultySettings};
+use scooter_rentals::game::{Command, Game, GameError, Outcome, Phase};
+use scooter_rentals::io::{SaveFile, SaveSlots, SlotInfo};
+use scooter_rentals::location::{Location, Locations};
+use scooter_rentals::period::{Period, Periods};
+use scooter_rentals::report::Report;
+use scooter_rentals::weather::{ForecastTime, FORECAST_DAYS};
 use crate::cli::{output, get_input_string, get_input_u32, get_input_nothing,
-                 get_input_f32, say_any_key};
-use crate::io::SaveFile;
+                 say_any_key};
 
 const USE_ANY_KEY_LABEL: bool = true;
-const CHANCE_SCOOTER_BREAKS: f32 = 0.05;
-const DAYS_PER_SEASON: u8 = 6;
-const FORECAST_ACCURACY: f32 = 0.70;
-const OPTIMAL_RENTAL_PRICE: f32 = 15.0;
-const PRICE_OF_ADVERTS: f32 = 5.0;
-const PRICE_OF_SCOOTERS: f32 = 100.0;
-const PRICE_OF_PARTS: f32 = 25.0;
-const ADVERT_EFFECT: f32 = 0.1;
-const STARTING_CASH: f32 = 100.0;
-const STARTING_SCOOTERS: u32 = 10;
-enum GameStatus { RUNNING, QUIT }
+const CLIMATE_DIR: &str = "climates";
+enum GameStatus { Running, Quit }
 
 fn main() {
-    let tmp_save_file_path = "scooter_save.ron";
+    let save_dir = "saves";
+    let legacy_save_file_path = "scooter_save.ron";
+    let config_file_path = "scooter_config.ron";
     // Startup
-    let mut day_num: u32;
-    let mut business: Business;
-    let mut weather: Weather;
     output("Scooter Rentals ™️".to_string());
 
-    match SaveFile::load_save_file(tmp_save_file_path) {
-        Ok(saved) => {
-            business = Business::new(saved.name)
-                .with_cash(saved.cash)
-                .with_working_scooters(saved.scooters_working)
-                .with_broken_scooters(saved.scooters_broken)
-                .with_scooter_parts(saved.scooter_parts)
-                .with_num_adverts(saved.num_advertisements);
-
-            weather = Weather::new()
-                .with_curent_weather(saved.current.as_str())
-                .with_forecast(saved.forecast.as_str())
-                .with_season(saved.season.as_str())
-                .with_temperature(saved.temperature.as_str())
-                .with_days_of_season(saved.days_of_season);
-
-            day_num = saved.day_num;
-            println!("Restoring saved game:{} on day {}.", business.name(), day_num);
-        }
-        Err(_) => {
-            output("What do you want your business to be called?".to_string());
-            let business_name = get_input_string().unwrap_or_else(|_error| {
-                output("That doesn't work. Let's use \"Rusty\"".to_string());
-                "Rusty Scooters".to_string()
-            });
-            day_num = 1;
-            business = Business::new(business_name);
-            weather = Weather::new();
-            output(format!("Opened a new Scooter business called {}!!", business.name()));
+    let config = match GameConfig::load_or_default(config_file_path) {
+        Ok(config) => config,
+        Err(err) => {
+            output(format!("Problem with {}: {}", config_file_path, err));
+            std::process::exit(1);
         }
+    };
+
+    let slots = SaveSlots::new(save_dir);
+    import_legacy_save(&slots, legacy_save_file_path);
+    let (mut game, slot) = start_menu(&slots, config);
+    if game.game_over().is_some() {
+        final_summary(&game);
+        return;
     }
 
     // Intro
     output("\n\n\n".to_string());
-    if day_num == 1 {
+    if game.day() == 1 {
         output("It's your first day.".to_string());
     }
 
     // Main Loop
     loop {
-        // Get basic info
-        output(format!("Day {day_num}."));
-        output(format!("{}",weather.describe(ForecastTime::Today)));
-        // Do the day's rentals
-        let mut cost_per = 0.0;
-        loop {
-            output("How much do you want to charge for each rental today?".to_string());
-            match get_input_f32() {
-                Ok(val) => {
-                    if val < 0.0 {
-                        output("Nope. That is not a positive number. Give it another shot.".to_string());
-                    } else {
-                        cost_per = val;
-                        break;
-                    }
-                }
-                Err(_) => {
-                    output("Nope. That is not a real number. Give it another shot.".to_string());
-                }
-            }
-        }
-        // TODO: Should weather params be passed individually or just as Weather?
-        match business.rent_scooters(cost_per, weather.get_temperature(), weather.get_current()) {
-            Ok(receipt) => {
-                output(format!("You made ${} today!", receipt.profit()));
-                output(format!("{} scooters were broken today!", receipt.broken_scooters()));
-                say_any_key();
-                get_input_nothing();
-            },
-            // Should never get an error back so PANIC!!!
-            Err(_) => panic!("There's an error!!!"),
+        if game.phase() == Phase::Management {
+            output(format!("Carrying on with day {}, after the rentals.", game.day()));
+        } else {
+            rent_out(&mut game);
         }
 
         // Let the player manage and get ready for tomorrow
-        let game_status = main_menu(&mut business, &weather);
+        let game_status = main_menu(&mut game);
         match game_status {
-            GameStatus::RUNNING => {
+            GameStatus::Running => {
                 // New Day
-                day_num += 1;
-                business.new_day();
-                weather.new_day();
+                let day = game.day();
+                let ended = game.apply(Command::EndDay).expect("Day can end after management");
+                print_report(&format!("Day {} report", day), &game.report(day, day));
+                if let Outcome::DayEnded { season_report: Some(report), .. } = ended {
+                    let season = game.history().last().map(|record| record.season.describe()).unwrap_or_default();
+                    print_report(&format!("End of {} report", season), &report);
+                }
+                say_any_key();
+                get_input_nothing();
+                if let Err(err) = slots.save(&slot, &game.to_save_file()) {
+                    output(format!("Couldn't autosave the game: {}", err));
+                }
+                if game.game_over().is_some() {
+                    final_summary(&game);
+                    return;
+                }
             }
-            GameStatus::QUIT => break,
+            GameStatus::Quit => break,
         }
     }
 
     // Save File
-    let save_file = SaveFile::new(
-        day_num,
-        business.name(),
-        business.cash(),
-        business.working_scooters(),
-        business.broken_scooters(),
-        business.scooter_parts(),
-        business.advertisements(),
-        weather.get_current().describe(),
-        weather.get_forecast().describe(),
-        weather.get_temperature().describe(),
-        weather.get_season().describe(),
-        weather.get_days_of_season(),
-    );
-    save_file.write_save_file(tmp_save_file_path);
+    if let Err(err) = slots.save(&slot, &game.to_save_file()) {
+        output(format!("Couldn't save the game: {}", err));
+    }
     // Exit
-    let profit = business.cash() - STARTING_CASH;
+    let profit = game.report(1, game.day()).net_income();
     if profit > 0.0 {
         output(format!("You made a profit of {}", profit));
     } else if profit < 0.0 {
@@ -146,7 +97,270 @@ fn main() {
 
 }
 
-fn main_menu(business: &mut Business, weather: &Weather) -> GameStatus {
+/// Start the day, ask the player how to run each shop and rent the scooters out.
+fn rent_out(game: &mut Game) {
+    // Get basic info
+    if let Ok(Outcome::DayStarted { day, weather }) = game.apply(Command::StartDay) {
+        output(format!("Day {day}."));
+        output(weather.describe(ForecastTime::Today));
+    }
+    if game.business().in_debt() {
+        output(format!("You're ${:.2} overdrawn. The bank charges for every night you stay that way.",
+                       game.business().overdrawn()));
+    }
+    // Do the day's rentals
+    let mut deploy = Locations::all(0);
+    let mut prices = Locations::all(Periods::all(0.0));
+    let open: Vec<Location> = game.business().shops().map(|(at, _)| at).collect();
+    for at in open {
+        if game.business().shops().count() > 1 {
+            output(format!("At your {} shop:", at.describe()));
+        }
+        let mut last_price = None;
+        for period in Period::ALL {
+            let price = ask_price(period, last_price);
+            *prices.get_mut(at).get_mut(period) = price;
+            last_price = Some(price);
+        }
+        *deploy.get_mut(at) = ask_deploy(game.business().rentable_scooters(at));
+    }
+    match game.apply(Command::Rent { deploy, prices }) {
+        Ok(Outcome::Rented(receipts)) => {
+            for receipt in &receipts {
+                output(format!("At your {} shop:", receipt.location().describe()));
+                for period in receipt.periods() {
+                    output(format!("\t{}: {} at ${} while {}, made ${}.", period.period.describe(), period.rented,
+                                   period.cost_per, period.weather.describe(), period.profit));
+                }
+                output(format!("You had {} rentals from {} scooters.", receipt.rented(), receipt.deployed()));
+                for (segment, rented) in receipt.segments().iter() {
+                    output(format!("\t{}: {}", segment.describe(), rented));
+                }
+                output(format!("You made ${} today!", receipt.profit()));
+                output(format!("{} scooters were broken today!", receipt.broken_scooters()));
+            }
+            say_any_key();
+            get_input_nothing();
+        },
+        // Should never get an error back so PANIC!!!
+        _ => panic!("There's an error!!!"),
+    }
+}
+
+/// Saves from before save slots lived in a single file; move it in as a slot of its own.
+fn import_legacy_save(slots: &SaveSlots, file_path: &str) {
+    let slot = "scooter_save";
+    if !std::path::Path::new(file_path).is_file() || slots.exists(slot) { return }
+    match SaveFile::load_save_file(file_path) {
+        Ok(saved) => {
+            if slots.create(slot, &saved).is_ok() && std::fs::remove_file(file_path).is_ok() {
+                output(format!("Moved your saved game into the \"{}\" save.", slot));
+            }
+        }
+        Err(err) => output(format!("Couldn't bring in {}: {}", file_path, err)),
+    }
+}
+
+fn start_menu(slots: &SaveSlots, config: GameConfig) -> (Game, String) {
+    loop {
+        let saved = slots.list().unwrap_or_else(|err| {
+            output(format!("Couldn't look for saved games: {}", err));
+            Vec::new()
+        });
+        if saved.is_empty() {
+            return new_game(slots, config);
+        }
+        output("Saved games:".to_string());
+        list_slots(&saved);
+        output("What would you like to do?".to_string());
+        output("1) Load a saved game".to_string());
+        output("2) Start a new game".to_string());
+        output("3) Copy a saved game".to_string());
+        output("4) Delete a saved game".to_string());
+        match get_input_u32() {
+            Ok(1) => {
+                if let Some(slot) = choose_slot(&saved) {
+                    match slots.load(&slot) {
+                        Ok(save) => {
+                            let game = Game::from_save_file(save, config);
+                            output(format!("Restoring saved game:{} on day {}.", game.business().name(), game.day()));
+                            return (game, slot);
+                        }
+                        Err(err) => {
+                            output(format!("Couldn't load \"{}\": {}", slot, err));
+                            if let Some(game) = recover_menu(slots, &slot, config.clone()) {
+                                return (game, slot);
+                            }
+                        }
+                    }
+                }
+            },
+            Ok(2) => return new_game(slots, config),
+            Ok(3) => {
+                if let Some(from) = choose_slot(&saved) {
+                    output("What should the copy be called?".to_string());
+                    let to = get_input_string().unwrap_or_default();
+                    match slots.duplicate(&from, &to) {
+                        Ok(()) => output(format!("Copied \"{}\" to \"{}\".", from, to)),
+                        Err(err) => output(format!("Couldn't copy that save: {}", err)),
+                    }
+                }
+            },
+            Ok(4) => {
+                if let Some(slot) = choose_slot(&saved) {
+                    output(format!("Really delete \"{}\"? Type yes to delete it.", slot));
+                    if get_input_string().map(|answer| answer == "yes").unwrap_or(false) {
+                        match slots.delete(&slot) {
+                            Ok(()) => output(format!("Deleted \"{}\".", slot)),
+                            Err(err) => output(format!("Couldn't delete that save: {}", err)),
+                        }
+                    }
+                }
+            },
+            Ok(_) => output("That's not a thing you can do.".to_string()),
+            Err(_) => output("Use the numbers.".to_string()),
+        }
+        output("\n".to_string());
+    }
+}
+
+/// Offer the backups of a save that wouldn't load.
+fn recover_menu(slots: &SaveSlots, slot: &str, config: GameConfig) -> Option<Game> {
+    let backups = slots.backups(slot);
+    if backups.is_empty() {
+        output("There are no backups of that save.".to_string());
+        return None;
+    }
+    output("These backups were saved before it:".to_string());
+    for (num, summary) in &backups {
+        match summary {
+            Ok(summary) => output(format!("\t{}) {} on day {} with ${:.2}",
+                                          num, summary.name, summary.day_num, summary.cash)),
+            Err(err) => output(format!("\t{}) can't be loaded ({})", num, err)),
+        }
+    }
+    output("Which backup do you want to carry on from? Use its number, or anything else to go back.".to_string());
+    let num = get_input_u32().ok()?;
+    match slots.load_backup(slot, num as usize) {
+        Ok(save) => {
+            let game = Game::from_save_file(save, config);
+            output(format!("Restoring backup of {} on day {}.", game.business().name(), game.day()));
+            Some(game)
+        }
+        Err(err) => {
+            output(format!("Couldn't load that backup: {}", err));
+            None
+        }
+    }
+}
+
+fn list_slots(saved: &[SlotInfo]) {
+    for (num, info) in saved.iter().enumerate() {
+        match &info.summary {
+            Ok(summary) => output(format!("\t{}) {}: {} on day {} with ${:.2}{}",
+                                          num + 1, info.slot, summary.name, summary.day_num, summary.cash,
+                                          if summary.finished { " (finished)" } else { "" })),
+            Err(err) => output(format!("\t{}) {}: can't be loaded ({})", num + 1, info.slot, err)),
+        }
+    }
+}
+
+fn choose_slot(saved: &[SlotInfo]) -> Option<String> {
+    output("Which save? Use its number.".to_string());
+    match get_input_u32() {
+        Ok(num) if num >= 1 && num as usize <= saved.len() => Some(saved[num as usize - 1].slot.clone()),
+        _ => {
+            output("There's no save with that number.".to_string());
+            None
+        }
+    }
+}
+
+fn new_game(slots: &SaveSlots, mut config: GameConfig) -> (Game, String) {
+    output("What do you want your business to be called?".to_string());
+    let business_name = get_input_string().unwrap_or_else(|_error| {
+        output("That doesn't work. Let's use \"Rusty\"".to_string());
+        "Rusty Scooters".to_string()
+    });
+    // A seed can be given on the command line to replay a game
+    let seed = std::env::args().nth(1)
+        .and_then(|arg| arg.parse::<u64>().ok())
+        .unwrap_or_else(rand::random);
+    config.weather.climate = choose_climate();
+    let difficulty = choose_difficulty();
+    let game = Game::new_at_difficulty(business_name, seed, config, difficulty);
+    let slot = loop {
+        output(format!("What should this save be called? Press return for \"{}\".", game.business().name()));
+        let slot = match get_input_string() {
+            Ok(slot) if !slot.is_empty() => slot,
+            _ => game.business().name(),
+        };
+        match slots.create(&slot, &game.to_save_file()) {
+            Ok(()) => break slot,
+            Err(err) => output(format!("Can't save there: {}", err)),
+        }
+    };
+    output(format!("Opened a new Scooter business called {} in a {} climate on {}!!", game.business().name(),
+                   game.climate().climate.describe(), game.difficulty().describe()));
+    (game, slot)
+}
+
+fn choose_climate() -> ClimateProfile {
+    output("Where should the business be?".to_string());
+    for (num, climate) in Climate::ALL.iter().enumerate() {
+        output(format!("{}) somewhere {}", num + 1, climate.describe()));
+    }
+    let climate = match get_input_u32() {
+        Ok(num) if num >= 1 && num as usize <= Climate::ALL.len() => Climate::ALL[num as usize - 1],
+        _ => {
+            output("Let's stay somewhere temperate.".to_string());
+            Climate::Temperate
+        }
+    };
+    ClimateProfile::load_or_builtin(CLIMATE_DIR, climate).unwrap_or_else(|err| {
+        output(format!("Problem with the {} climate file, using the usual one: {}", climate.describe(), err));
+        climate.profile()
+    })
+}
+
+fn choose_difficulty() -> Difficulty {
+    output("How hard should it be?".to_string());
+    for (num, difficulty) in Difficulty::PRESETS.iter().enumerate() {
+        output(format!("{}) {}", num + 1, difficulty.describe()));
+    }
+    output(format!("{}) custom", Difficulty::PRESETS.len() + 1));
+    match get_input_u32() {
+        Ok(num) if num >= 1 && num as usize <= Difficulty::PRESETS.len() => Difficulty::PRESETS[num as usize - 1],
+        Ok(num) if num as usize == Difficulty::PRESETS.len() + 1 => Difficulty::Custom(DifficultySettings {
+            starting_cash: ask_percent("starting cash"),
+            prices: ask_percent("scooter and part prices"),
+            forecast_accuracy: ask_percent("forecast accuracy"),
+            breakage: ask_percent("chance of scooters breaking"),
+            demand: ask_percent("number of customers"),
+        }),
+        _ => {
+            output("Let's play it normal.".to_string());
+            Difficulty::Normal
+        }
+    }
+}
+
+/// Ask how much of the usual `what` there should be, as a percentage; return keeps it at 100%.
+fn ask_percent(what: &str) -> f32 {
+    loop {
+        output(format!("What percentage of the usual {} do you want? Press return for 100%.", what));
+        match get_input_string() {
+            Ok(val) if val.is_empty() => return 1.0,
+            Ok(val) => match val.trim_end_matches('%').parse::<f32>() {
+                Ok(percent) if percent.is_finite() && percent >= 0.0 => return percent / 100.0,
+                _ => output("Nope. That is not a positive number. Give it another shot.".to_string()),
+            },
+            Err(_) => output("Nope. That didn't work. Give it another shot.".to_string()),
+        }
+    }
+}
+
+fn main_menu(game: &mut Game) -> GameStatus {
     loop {
         output("What would you like to do?".to_string());
         // buy
@@ -157,23 +371,35 @@ fn main_menu(business: &mut Business, weather: &Weather) -> GameStatus {
         output("3) Repair broken scooters".to_string());
         // advertise
         output("4) Buy advertisements for tomorrow".to_string());
+        // expand
+        output("5) Open a shop somewhere new".to_string());
+        // transfer
+        output("6) Move scooters between shops".to_string());
+        // bank
+        output("7) Visit the bank".to_string());
         // get business info
-        output("5) Get info on your business and the weather".to_string());
+        output("8) Get info on your business and the weather".to_string());
+        // reports
+        output("9) See how today and this season are going".to_string());
         // ready
-        output("6) Ready to move on to the next day".to_string());
+        output("10) Ready to move on to the next day".to_string());
         // quit
-        output("7) Quit the game. ".to_string());
+        output("11) Quit the game. ".to_string());
         let input = get_input_u32();
         match input {
             Ok(n) => {
                 match n {
-                    1 => buy_submenu(business),
-                    2 => sell_submenu(business),
-                    3 => repair_submenu(business),
-                    4 => advert_submenu(business),
-                    5 => get_business_info(business, weather),
-                    6 => return GameStatus::RUNNING,
-                    7 => return GameStatus::QUIT,
+                    1 => buy_submenu(game),
+                    2 => sell_submenu(game),
+                    3 => repair_submenu(game),
+                    4 => advert_submenu(game),
+                    5 => open_location_submenu(game),
+                    6 => transfer_submenu(game),
+                    7 => bank_submenu(game),
+                    8 => get_business_info(game),
+                    9 => reports_submenu(game),
+                    10 => return GameStatus::Running,
+                    11 => return GameStatus::Quit,
                     _ => output("That's not a thing you can do.".to_string()),
                 }
             },
@@ -183,42 +409,297 @@ fn main_menu(business: &mut Business, weather: &Weather) -> GameStatus {
     }
 }
 
-fn get_business_info(business: &mut Business, weather: &Weather) {
-    output(format!("{} Scooter shop has:", business.name()));
+/// Ask how many of the `rentable` scooters to put out; return puts them all out.
+fn ask_deploy(rentable: u32) -> u32 {
+    loop {
+        output(format!("How many of your {} charged scooters do you want to put out? Press return for all of them.", rentable));
+        match get_input_string() {
+            Ok(val) if val.is_empty() => return rentable,
+            Ok(val) => match val.parse::<u32>() {
+                Ok(num) if num <= rentable => return num,
+                Ok(_) => output(format!("You only have {} to put out. Give it another shot.", rentable)),
+                Err(_) => output("Nope. That is not a whole number. Give it another shot.".to_string()),
+            },
+            Err(_) => output("Nope. That didn't work. Give it another shot.".to_string()),
+        }
+    }
+}
+
+/// Ask which open shop to use, skipping the question when there's only one.
+fn choose_location(game: &Game) -> Option<Location> {
+    let open: Vec<Location> = game.business().shops().map(|(at, _)| at).collect();
+    if open.len() == 1 {
+        return open.first().copied();
+    }
+    output("Which shop?".to_string());
+    for (num, at) in open.iter().enumerate() {
+        output(format!("{}) {}", num + 1, at.describe()));
+    }
+    match get_input_u32() {
+        Ok(num) if num >= 1 && num as usize <= open.len() => Some(open[num as usize - 1]),
+        _ => {
+            output("You don't have a shop with that number.".to_string());
+            None
+        }
+    }
+}
+
+/// Ask what to charge during `period`; return keeps the `previous` period's price.
+fn ask_price(period: Period, previous: Option<f32>) -> f32 {
+    loop {
+        match previous {
+            Some(price) => output(format!("How much do you want to charge for each rental in the {}? Press return for ${}.",
+                                          period.describe(), price)),
+            None => output(format!("How much do you want to charge for each rental in the {}?", period.describe())),
+        }
+        match get_input_string() {
+            Ok(val) if val.is_empty() && previous.is_some() => return previous.unwrap_or_default(),
+            Ok(val) => match val.parse::<f32>() {
+                Ok(price) if price.is_finite() && price >= 0.0 => return price,
+                Ok(_) => output("Nope. That is not a positive number. Give it another shot.".to_string()),
+                Err(_) => output("Nope. That is not a real number. Give it another shot.".to_string()),
+            },
+            Err(_) => output("Nope. That didn't work. Give it another shot.".to_string()),
+        }
+    }
+}
+
+fn describe_prices(prices: Periods<f32>) -> String {
+    if prices == Periods::all(prices.morning) {
+        format!("${}", prices.morning)
+    } else {
+        prices.iter().map(|(_, price)| format!("${}", price)).collect::<Vec<_>>().join("/")
+    }
+}
+
+fn get_business_info(game: &Game) {
+    let business = game.business();
+    let weather = game.weather();
+    output(format!("{} Scooter business, somewhere {}, playing on {}, has:", business.name(),
+                   game.climate().climate.describe(), game.difficulty().describe()));
     // Cash
     output(format!("\t${} cash.", business.cash()));
-    // Working Scooters
-    output(format!("\t{} working scooters, ready to rent.", business.working_scooters()));
-    // Broken Scooters
-    output(format!("\t{} broken scooters, unrentable until repaired.", business.broken_scooters()));
+    // Running Costs
+    output(format!("\t${} to pay each night in rent, wages and storage, and ${} insurance when the season ends.",
+                   business.daily_costs(), business.season_insurance()));
+    // Bank
+    describe_bank(game);
     // Scooter Parts
-    output(format!("\t{} parts for repairing scooters.", business.scooter_parts()));
-    // Num Adverts
-    output(format!("\t{} advertisements ready for tomorrow.", business.advertisements()));
+    output(format!("\t{} parts for repairing scooters, shared by every shop.", business.scooter_parts()));
+    // Shops
+    for (at, shop) in business.shops() {
+        output(format!("\tA {} shop, paying ${} rent a day:", at.describe(), game.config().locations.get(at).rent));
+        let fleet = shop.fleet();
+        // Working Scooters
+        output(format!("\t\t{} working scooters, ready to rent.", fleet.working()));
+        // Broken Scooters
+        output(format!("\t\t{} broken scooters, unrentable until repaired.", fleet.broken()));
+        // Fleet Condition
+        output(format!("\t\t{} charged enough to rent. Fleet averages {:.1} days old, {:.0}% wear, {:.0}% battery.",
+                       business.rentable_scooters(at), fleet.average_age(business.day()),
+                       fleet.average_wear() * 100.0, fleet.average_battery() * 100.0));
+        for scooter in fleet.scooters() {
+            output(format!("\t\t\t#{}: {} days old, {:.0} km, {:.0}% wear, {:.0}% battery, {:?}",
+                           scooter.id(), scooter.age(business.day()), scooter.mileage(),
+                           scooter.wear() * 100.0, scooter.battery() * 100.0, scooter.status()));
+        }
+        // Num Adverts
+        output(format!("\t\t{} advertisements ready for tomorrow.", shop.advertisements()));
+        // Last Day
+        let last = game.history().last()
+            .and_then(|record| record.receipts.iter().find(|receipt| receipt.location() == at));
+        if let Some(receipt) = last {
+            output(format!("\t\tLast day it rented {} of {} scooters for ${}, and {} broke.",
+                           receipt.rented(), receipt.deployed(), receipt.profit(), receipt.broken_scooters()));
+        }
+    }
+    // Recent Days
+    let days = game.history().days();
+    if !days.is_empty() {
+        output("\tRecent days:".to_string());
+        for record in &days[days.len().saturating_sub(5)..] {
+            let charged: Vec<String> = record.receipts.iter()
+                .map(|receipt| format!("{} at {}", describe_prices(record.prices.get(receipt.location())),
+                                       receipt.location().describe()))
+                .collect();
+            output(format!("\t\tDay {}: {} {}, charged {}, rented {} for ${}, {} broke.",
+                           record.day, record.temperature.describe(), record.weather.describe(), charged.join(", "),
+                           record.rented(), record.profit(), record.broken_scooters()));
+        }
+    }
     // Weather Today
     output(weather.describe(ForecastTime::Today));
-    // Weather Tomorrow
+    // Weather for the week ahead
     output(weather.describe(ForecastTime::Tomorrow));
+    for days in 2..=FORECAST_DAYS {
+        output(weather.describe(ForecastTime::InDays(days)));
+    }
+    say_any_key();
+    get_input_nothing();
+}
+
+fn describe_bank(game: &Game) {
+    let business = game.business();
+    let bank = business.bank();
+    if !bank.loans().is_empty() {
+        output(format!("\t${:.2} owed to the bank, paying ${:.2} a day.", bank.owed(), bank.daily_payments()));
+        for loan in bank.loans() {
+            output(format!("\t\tBorrowed ${:.2} on day {}: ${:.2} left, {} payments of ${:.2} to go.",
+                           loan.principal, loan.day_taken, loan.balance, loan.payments_left, loan.daily_payment));
+        }
+    }
+    output(format!("\tCredit limit ${:.2}, with ${:.2} of it left to borrow and an overdraft of up to ${:.2}.",
+                   business.credit_limit(), business.credit_available(), business.overdraft_limit()));
+}
+
+fn bank_submenu(game: &mut Game) {
+    let terms = game.config().bank;
+    output(format!("You have ${:.2} cash.", game.business().cash()));
+    describe_bank(game);
+    output(format!("Loans are paid back over {} days at {:.1}% interest a day.", terms.loan_term_days,
+                   terms.loan_daily_rate * 100.0));
+    output("1) Take out a loan".to_string());
+    output("2) Pay back loans early".to_string());
+    output("3) Go back to the main menu.".to_string());
+    match get_input_u32() {
+        Ok(1) => {
+            output("How much do you want to borrow?".to_string());
+            match get_input_u32() {
+                Ok(amount) => match game.apply(Command::TakeLoan(amount as f32)) {
+                    Ok(Outcome::TookLoan { amount, daily_payment, days }) => output(format!(
+                        "Borrowed ${}. You'll pay ${:.2} a day for {} days.", amount, daily_payment, days)),
+                    Err(GameError::Management(ManagementError::OverCreditLimit)) => output(format!(
+                        "The bank will only lend you another ${:.2}.", game.business().credit_available())),
+                    _ => output("The bank won't lend you that.".to_string()),
+                },
+                Err(_) => output("That's not a real number.".to_string()),
+            }
+        },
+        Ok(2) => {
+            output(format!("You owe ${:.2}. How much do you want to pay back?", game.business().bank().owed()));
+            match get_input_u32() {
+                Ok(amount) => match game.apply(Command::RepayLoans(amount as f32)) {
+                    Ok(Outcome::RepaidLoans { amount }) => output(format!("Paid back ${:.2}.", amount)),
+                    _ => output("You don't have the cash to pay that back.".to_string()),
+                },
+                Err(_) => output("That's not a real number.".to_string()),
+            }
+        },
+        Ok(3) => return,
+        _ => output("That's not a thing you can do.".to_string()),
+    }
     say_any_key();
     get_input_nothing();
 }
 
-fn advert_submenu(business: &mut Business) {
+fn reports_submenu(game: &Game) {
+    print_report("Today so far", &game.report(game.day(), game.day()));
+    print_report(&format!("This {} so far", game.weather().get_season().describe()),
+                 &game.report(game.season_start(), game.day()));
+    say_any_key();
+    get_input_nothing();
+}
+
+fn final_summary(game: &Game) {
+    let business = game.business();
+    output("\n\n\nGame over!".to_string());
+    if let Some(reason) = game.game_over() {
+        output(reason.describe());
+    }
+    output(format!("{} Scooter business ran for {} days.", business.name(), game.history().days().len()));
+    output(format!("\tIt finished with ${:.2} cash and owed the bank ${:.2}.", business.cash(), business.bank().owed()));
+    output(format!("\tIt had {} scooters, and was worth ${:.2} all told.",
+                   business.working_scooters() + business.broken_scooters(),
+                   business.net_worth(game.scooter_resale_price())));
+    print_report("Over the whole game", &game.report(1, game.day()));
+}
+
+fn print_report(title: &str, report: &Report) {
+    output(format!("{}:", title));
+    output(format!("\tRevenue from rentals: ${:.2}", report.revenue));
+    output(format!("\tScooters bought, less any sold: ${:.2}", report.scooters));
+    output(format!("\tRepair parts: ${:.2}", report.parts));
+    output(format!("\tAdvertising: ${:.2}", report.advertising));
+    output(format!("\tOperating costs: ${:.2}", report.operating));
+    output(format!("\tInterest and bank fees: ${:.2}", report.financing));
+    output(format!("\tNet income: ${:.2}", report.net_income()));
+    output(format!("\t{} rentals from {} scooters put out, {:.0}% utilization.",
+                   report.rented, report.deployed, report.utilization() * 100.0));
+    output(format!("\t{} broke down, {:.0}% of rentals.", report.broken, report.breakage_rate() * 100.0));
+}
+
+fn open_location_submenu(game: &mut Game) {
+    let closed: Vec<Location> = Location::ALL.into_iter().filter(|at| !game.business().is_open(*at)).collect();
+    if closed.is_empty() {
+        output("You already have a shop everywhere you can.".to_string());
+    } else {
+        output(format!("You have ${} cash. Where do you want to open a shop?", game.business().cash()));
+        for (num, at) in closed.iter().enumerate() {
+            let costs = game.config().locations.get(*at);
+            output(format!("{}) {}: ${} to open, then ${} rent a day", num + 1, at.describe(), costs.open_cost, costs.rent));
+        }
+        match get_input_u32() {
+            Ok(num) if num >= 1 && num as usize <= closed.len() => {
+                match game.apply(Command::OpenLocation(closed[num as usize - 1])) {
+                    Ok(Outcome::OpenedLocation { at, cost }) => output(format!(
+                        "Opened a shop at the {} for ${}. Buy scooters for it, or move some over.", at.describe(), cost)),
+                    _ => output("You can't afford to open a shop there.".to_string()),
+                }
+            },
+            _ => output("That's not somewhere you can open a shop.".to_string()),
+        }
+    }
+    say_any_key();
+    get_input_nothing();
+}
+
+fn transfer_submenu(game: &mut Game) {
+    if game.business().shops().count() < 2 {
+        output("You need at least two shops to move scooters between them.".to_string());
+        say_any_key();
+        get_input_nothing();
+        return;
+    }
+    output("Move scooters from which shop?".to_string());
+    let Some(from) = choose_location(game) else { return };
+    output("And to which shop?".to_string());
+    let Some(to) = choose_location(game) else { return };
+    let working = game.business().shop(from).map(|shop| shop.fleet().working()).unwrap_or(0);
+    let price = game.config().prices.transfer;
+    output(format!("The {} shop has {} working scooters. Moving one costs ${}. How many?", from.describe(), working, price));
+    match get_input_u32() {
+        Ok(num) => match game.apply(Command::TransferScooters { from, to, num }) {
+            Ok(Outcome::TransferredScooters { num, cost, .. }) => output(format!(
+                "Moved {} scooters to the {} shop for ${}.", num, to.describe(), cost)),
+            Err(GameError::Management(ManagementError::NotEnoughMoney)) => output("You can't afford that.".to_string()),
+            _ => output(format!("You can't move {} scooters from the {} shop to the {} shop.",
+                                num, from.describe(), to.describe())),
+        },
+        Err(_) => output("That's not a real number.".to_string()),
+    }
+    say_any_key();
+    get_input_nothing();
+}
+
+fn advert_submenu(game: &mut Game) {
+    let Some(at) = choose_location(game) else { return };
+    let business = game.business();
+    let price_of_adverts = game.config().prices.advert;
     output(format!("You have ${} cash.", business.cash()));
-    output(format!("Each advertisement costs ${}.", PRICE_OF_ADVERTS));
-    if business.cash() > PRICE_OF_ADVERTS {
-        output("How many advertisements do you want to buy for tomorrow?".to_string());
+    output(format!("Each advertisement costs ${}.", price_of_adverts));
+    if business.spending_power() > price_of_adverts {
+        output(format!("How many advertisements do you want to buy for the {} shop tomorrow?", at.describe()));
         let num_res = get_input_u32();
         match num_res {
             Ok(num) => {
                 let mut num = num as f32;
-                let cost = PRICE_OF_ADVERTS * num;
-                if cost > business.cash() {
-                    num = multiples_within_f32(business.cash(), PRICE_OF_ADVERTS);
+                let cost = price_of_adverts * num;
+                if cost > business.spending_power() {
+                    num = multiples_within_f32(business.spending_power(), price_of_adverts);
                     output(format!("You can only afford {}.", num));
                 }
-                business.buy_advertisements(num as u32, PRICE_OF_ADVERTS).unwrap();
-                output(format!("Bought {} advertisements for ${} each.", num, PRICE_OF_ADVERTS));
+                game.apply(Command::BuyAdvertisements { at, num: num as u32 }).unwrap();
+                output(format!("Bought {} advertisements for ${} each.", num, price_of_adverts));
             }
             Err(_) => {
                 output("That's not a valid number.".to_string());
@@ -232,14 +713,17 @@ fn advert_submenu(business: &mut Business) {
     get_input_nothing();
 }
 
-fn repair_submenu(business: &mut Business) {
-    let reparable = u32::min(business.broken_scooters(), business.scooter_parts());
-    output(format!("You have enough parts to repair {} of your broken scooters.", reparable));
+fn repair_submenu(game: &mut Game) {
+    let Some(at) = choose_location(game) else { return };
+    let business = game.business();
+    let broken = business.shop(at).map(|shop| shop.fleet().broken()).unwrap_or(0);
+    let reparable = u32::min(broken, business.scooter_parts());
+    output(format!("You have enough parts to repair {} of the broken scooters at the {} shop.", reparable, at.describe()));
     output("How many do you want to repair?".to_string());
     match get_input_u32() {
         Ok(mut num) => {
             num = u32::min(num, reparable);
-            business.repair_scooters(num).expect("Scooters are repairable");
+            game.apply(Command::RepairScooters { at, num }).expect("Scooters are repairable");
             output(format!("You repaired {} scooters.", num));
             say_any_key();
             get_input_nothing();
@@ -252,20 +736,23 @@ fn repair_submenu(business: &mut Business) {
     }
 }
 
-fn sell_submenu(business: &mut Business) {
-    output(format!("You have {} working scooters you could sell.", business.working_scooters()));
-    let price: f32 = PRICE_OF_SCOOTERS / 2.0;
-    output(format!("You can get {} for each one.", price));
+fn sell_submenu(game: &mut Game) {
+    let Some(at) = choose_location(game) else { return };
+    let working = game.business().shop(at).map(|shop| shop.fleet().working()).unwrap_or(0);
+    output(format!("The {} shop has {} working scooters you could sell.", at.describe(), working));
+    let price: f32 = game.scooter_resale_price();
+    output(format!("You can get up to {} for each one, less for worn ones. The most worn go first.", price));
     output("How many would you like to sell?".to_string());
     match get_input_u32() {
         Ok(num) => {
             let mut num = num;
-            if num > business.working_scooters() {
-                num = business.working_scooters();
+            if num > working {
+                num = working;
                 output(format!("You can only have {} to sell.", num));
             }
-            business.sell_working_scooters(num, price).unwrap();
-            output(format!("Sold {} scooters for ${}.", num, price * num as f32));
+            if let Ok(Outcome::SoldScooters { num, income, .. }) = game.apply(Command::SellScooters { at, num }) {
+                output(format!("Sold {} scooters for ${:.2}.", num, income));
+            }
             say_any_key();
             get_input_nothing();
         },
@@ -277,8 +764,11 @@ fn sell_submenu(business: &mut Business) {
     }
 }
 
-fn buy_submenu(business: &mut Business) {
-    output(format!("You have {} cash on hand.", business.cash()));
+fn buy_submenu(game: &mut Game) {
+    let business = game.business();
+    let prices = game.config().prices;
+    output(format!("You have {} cash on hand, and can spend {} with your overdraft.",
+                   business.cash(), business.spending_power()));
     output("Ok, what do you want to buy?".to_string());
     output("1) New Scooters?".to_string());
     output("2) Scooter parts?".to_string());
@@ -288,38 +778,39 @@ fn buy_submenu(business: &mut Business) {
         Ok(choice) => {
             match choice {
                 1 => {
-                    output(format!("Ok, scooters cost {}. How many?", PRICE_OF_SCOOTERS));
+                    let Some(at) = choose_location(game) else { return };
+                    output(format!("Ok, scooters cost {}. How many for the {} shop?", prices.scooter, at.describe()));
                     match get_input_u32() {
                         Ok(num) => {
                             let mut num = num as f32;
-                            let cost = PRICE_OF_SCOOTERS * num;
-                            if cost > business.cash() {
-                                num = multiples_within_f32(business.cash(), PRICE_OF_SCOOTERS);
+                            let cost = prices.scooter * num;
+                            if cost > business.spending_power() {
+                                num = multiples_within_f32(business.spending_power(), prices.scooter);
                                 output(format!("You can only afford {}.", num));
                             }
-                            business.buy_scooters(num as u32, PRICE_OF_SCOOTERS).unwrap();
+                            game.apply(Command::BuyScooters { at, num: num as u32 }).unwrap();
                             output(format!("Bought {} scooters.", num));
                             say_any_key();
                             get_input_nothing();
                         },
                         Err(_) => {
-                            output(format!("That's not a real number."));
+                            output("That's not a real number.".to_string());
                             say_any_key();
                             get_input_nothing();
                         },
                     }
                 },
                 2 => {
-                    output(format!("Ok, parts cost {}. How many?", PRICE_OF_PARTS));
+                    output(format!("Ok, parts cost {}. How many?", prices.part));
                     match get_input_u32() {
                         Ok(num) => {
                             let mut num = num as f32;
-                            let cost = PRICE_OF_PARTS * num;
-                            if cost > business.cash() {
-                                num = multiples_within_f32(business.cash(), PRICE_OF_PARTS);
+                            let cost = prices.part * num;
+                            if cost > business.spending_power() {
+                                num = multiples_within_f32(business.spending_power(), prices.part);
                                 output(format!("You can only afford {}.", num));
                             }
-                            business.buy_scooter_parts(num as u32, PRICE_OF_PARTS).unwrap();
+                            game.apply(Command::BuyParts(num as u32)).unwrap();
                             output(format!("Bought {} parts.", num));
                             say_any_key();
                             get_input_nothing();
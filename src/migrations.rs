@@ -0,0 +1,217 @@
+//! Older save file layouts and the steps that upgrade each one to the next.
+//!
+//! Every layout that has ever been released keeps a struct here, built only
+//! from plain values and its own types, so changes to the game's types can't
+//! change how an old save is read. A save from any version can then be walked
+//! forward to the current `SaveFile`. When `SaveFile` changes, the layout it
+//! replaces moves here along with frozen copies of the types it uses.
+
+use std::str::FromStr;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use crate::bank::Bank;
+use crate::climate::Climate;
+use crate::difficulty::Difficulty;
+use crate::fleet::{Fleet, Scooter, ScooterStatus};
+use crate::game::Phase;
+use crate::history::History;
+use crate::io::{SaveError, SaveFile, SAVE_VERSION};
+use crate::ledger::Ledger;
+use crate::location::{Location, Locations, Shop};
+use crate::period::Periods;
+use crate::rng::seeded;
+use crate::weather::{Conditions, Season, Temperature, Weather, WeatherType};
+
+/// Just enough of a save file to find out which layout it uses.
+/// Saves from before versioning have no `version` field and count as version 0.
+#[derive(Deserialize)]
+struct VersionProbe {
+    #[serde(default)]
+    version: u32,
+}
+
+pub(crate) fn upgrade(ron_text: &str) -> Result<SaveFile, SaveError> {
+    let probe: VersionProbe = ron::from_str(ron_text)?;
+    match probe.version {
+        0 => ron::from_str::<SaveFileV0>(ron_text)?.upgrade().map(Upgrade::upgrade_all),
+        SAVE_VERSION => walk::<SaveFile>(ron_text),
+        version => Err(SaveError::UnsupportedVersion(version)),
+    }
+}
+
+/// Read a save in layout `T` and walk it forward to the current one.
+fn walk<T: Upgrade + DeserializeOwned>(ron_text: &str) -> Result<SaveFile, SaveError> {
+    Ok(ron::from_str::<T>(ron_text)?.upgrade_all())
+}
+
+/// One step from a save layout to the one after it. Once a save has been
+/// read every step always succeeds, so it's walked forward one step at a time
+/// until it reaches the current `SaveFile`. A new layout only needs the step
+/// from the one before it.
+trait Upgrade: Sized {
+    type Next: Upgrade;
+
+    fn upgrade(self) -> Self::Next;
+
+    fn upgrade_all(self) -> SaveFile { self.upgrade().upgrade_all() }
+}
+
+impl Upgrade for SaveFile {
+    type Next = SaveFile;
+
+    fn upgrade(self) -> SaveFile { self }
+
+    fn upgrade_all(self) -> SaveFile { self }
+}
+
+/// The original, unversioned layout: plain scooter counts, no random seed and
+/// the weather written as free-form words.
+#[derive(Deserialize, Debug)]
+struct SaveFileV0 {
+    day_num: u32,
+    name: String,
+    cash: f32,
+    scooters_working: u32,
+    scooters_broken: u32,
+    scooter_parts: u32,
+    num_advertisements: u32,
+    current: String,
+    forecast: String,
+    temperature: String,
+    season: String,
+    days_of_season: u8,
+}
+
+/// Turn a saved weather word into its type, refusing anything unknown
+/// rather than guessing.
+fn parse<T: FromStr<Err = impl std::fmt::Display>>(text: &str) -> Result<T, SaveError> {
+    text.parse().map_err(|err: T::Err| SaveError::InvalidValue(err.to_string()))
+}
+
+impl SaveFileV0 {
+    /// Counts become brand new scooters bought on day 1 at a downtown shop,
+    /// and the ledger opens with the cash on hand. The game is given a fresh
+    /// seed since it never had one, and history starts from here. Today's
+    /// readings are typical for its weather, the week ahead is made up with
+    /// the new generator, and tomorrow keeps the forecast the player saw.
+    /// Everything else is as it was before it could change: a temperate
+    /// business on normal difficulty, at the start of a day.
+    fn upgrade(self) -> Result<SaveFile, SaveError> {
+        let current: WeatherType = parse(&self.current)?;
+        let forecast: WeatherType = parse(&self.forecast)?;
+        let temperature: Temperature = parse(&self.temperature)?;
+        let season: Season = parse(&self.season)?;
+
+        let mut fleet = Fleet::new(self.scooters_working, 1);
+        for id in self.scooters_working..self.scooters_working + self.scooters_broken {
+            fleet.push(Scooter::new(id, 1).with_status(ScooterStatus::Broken));
+        }
+        let mut shops = Locations::default();
+        *shops.get_mut(Location::Downtown) = Some(Shop::new(fleet).with_num_adverts(self.num_advertisements));
+
+        let seed = rand::random();
+        let mut rng = seeded(seed);
+        let mut weather = Weather::default()
+            .with_conditions(Conditions::typical(current, temperature))
+            .with_period_weather(Periods::all(current))
+            .with_season(season)
+            .with_days_of_season(self.days_of_season);
+        weather.new_outlook(&mut rng);
+        let weather = weather.with_forecast(forecast);
+
+        Ok(SaveFile::new(
+            self.day_num,
+            seed,
+            rng,
+            self.name,
+            Ledger::opening(self.day_num, self.cash),
+            shops,
+            self.scooter_parts,
+            Bank::default(),
+            weather,
+            History::default(),
+            0,
+            None,
+            Difficulty::Normal,
+            Climate::Temperate.profile(),
+            Phase::DayStart,
+            None,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod migration_tests {
+    use crate::climate::Climate;
+    use crate::difficulty::Difficulty;
+    use crate::game::Phase;
+    use crate::io::{SaveError, SaveFile, SAVE_VERSION};
+    use crate::period::Period;
+    use crate::weather::{Conditions, Season, Temperature, WeatherType};
+
+    const V0_SAVE: &str = r#"(
+    day_num: 4,
+    name: "Zoom",
+    cash: 340.0,
+    scooters_working: 9,
+    scooters_broken: 1,
+    scooter_parts: 2,
+    num_advertisements: 0,
+    current: "sunny",
+    forecast: "rainy",
+    temperature: "hot",
+    season: "spring",
+    days_of_season: 3,
+)"#;
+
+    #[test]
+    fn unversioned_save_is_upgraded() {
+        let save = SaveFile::from_ron(V0_SAVE).unwrap();
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.day_num, 4);
+        assert_eq!(save.ledger.cash(), 340.0);
+        assert_eq!(save.ledger.transactions().len(), 1);
+        assert!(save.bank.loans().is_empty());
+        assert_eq!(save.game_over, None);
+        assert_eq!(save.difficulty, Difficulty::Normal);
+        assert_eq!(save.climate, Climate::Temperate.profile());
+        let downtown = save.shops.downtown.as_ref().unwrap();
+        assert_eq!(downtown.fleet().working(), 9);
+        assert_eq!(downtown.fleet().broken(), 1);
+        assert!(save.shops.beach.is_none());
+        assert_eq!(save.scooter_parts, 2);
+        assert!(save.history.days().is_empty());
+        assert_eq!(save.weather.get_current(), WeatherType::Sunny);
+        assert_eq!(save.weather.get_period(Period::Night), WeatherType::Sunny);
+        assert_eq!(save.weather.get_conditions(), Conditions::typical(WeatherType::Sunny, Temperature::Hot));
+        assert_eq!(save.weather.get_forecast(), WeatherType::Rainy);
+        assert_eq!(save.weather.get_temperature(), Temperature::Hot);
+        assert_eq!(save.weather.get_season(), Season::Spring);
+        assert_eq!(save.weather.get_days_of_season(), 3);
+        assert_eq!(save.phase, Phase::DayStart);
+        assert!(save.today.is_none());
+    }
+
+    #[test]
+    fn misspelled_weather_is_reported() {
+        let text = V0_SAVE.replace("\"rainy\"", "\"rainyy\"");
+        match SaveFile::from_ron(text.as_str()) {
+            Err(SaveError::InvalidValue(problem)) => assert!(problem.contains("rainyy")),
+            other => panic!("Expected invalid value, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn future_version_is_unsupported() {
+        let text = format!("(version: {}, day_num: 1)", SAVE_VERSION + 1);
+        match SaveFile::from_ron(text.as_str()) {
+            Err(SaveError::UnsupportedVersion(version)) => assert_eq!(version, SAVE_VERSION + 1),
+            other => panic!("Expected unsupported version, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn garbage_is_a_parse_error() {
+        assert!(matches!(SaveFile::from_ron("(day_num: "), Err(SaveError::Parse(_))));
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+/// The parts of a day, each rented out separately.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Morning,
+    Midday,
+    Evening,
+    Night,
+}
+
+impl Period {
+    /// Every period, from morning to night.
+    pub const ALL: [Period; 4] = [Period::Morning, Period::Midday, Period::Evening, Period::Night];
+
+    pub fn describe(&self) -> String {
+        match self {
+            Period::Morning => "morning rush".to_string(),
+            Period::Midday => "middle of the day".to_string(),
+            Period::Evening => "evening".to_string(),
+            Period::Night => "night".to_string(),
+        }
+    }
+}
+
+/// One value for each `Period` of the day, in the order they happen.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Periods<T> {
+    pub morning: T,
+    pub midday: T,
+    pub evening: T,
+    pub night: T,
+}
+
+impl<T: Copy> Periods<T> {
+    /// The same value for the whole day.
+    pub fn all(val: T) -> Self {
+        Self { morning: val, midday: val, evening: val, night: val }
+    }
+
+    pub fn get(&self, period: Period) -> T {
+        match period {
+            Period::Morning => self.morning,
+            Period::Midday => self.midday,
+            Period::Evening => self.evening,
+            Period::Night => self.night,
+        }
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(Period, T) -> U) -> Periods<U> {
+        Periods {
+            morning: f(Period::Morning, self.morning),
+            midday: f(Period::Midday, self.midday),
+            evening: f(Period::Evening, self.evening),
+            night: f(Period::Night, self.night),
+        }
+    }
+
+    /// Each period with its value, from morning to night.
+    pub fn iter(&self) -> impl Iterator<Item = (Period, T)> {
+        [
+            (Period::Morning, self.morning),
+            (Period::Midday, self.midday),
+            (Period::Evening, self.evening),
+            (Period::Night, self.night),
+        ].into_iter()
+    }
+}
+
+impl<T> Periods<T> {
+    pub fn get_mut(&mut self, period: Period) -> &mut T {
+        match period {
+            Period::Morning => &mut self.morning,
+            Period::Midday => &mut self.midday,
+            Period::Evening => &mut self.evening,
+            Period::Night => &mut self.night,
+        }
+    }
+}
+
+#[cfg(test)]
+mod period_tests {
+    use crate::period::{Period, Periods};
+
+    #[test]
+    fn periods_run_morning_to_night() {
+        let order: Vec<Period> = Periods::all(0).iter().map(|(period, _)| period).collect();
+        assert_eq!(order, Period::ALL);
+    }
+
+    #[test]
+    fn get_and_get_mut_agree() {
+        let mut prices = Periods::all(10.0);
+        *prices.get_mut(Period::Evening) = 14.0;
+        assert_eq!(prices.get(Period::Evening), 14.0);
+        assert_eq!(prices.map(|_, price| price * 2.0).get(Period::Night), 20.0);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::history::DayRecord;
+use crate::ledger::{Category, Ledger};
+use crate::period::Period;
+
+/// How the business did over a run of days: an income statement from the
+/// ledger, and how hard the scooters were worked from the day records.
+///
+/// Costs are positive amounts spent; scooter sales count against the cost of scooters.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Report {
+    pub first_day: u32,
+    pub last_day: u32,
+    pub revenue: f32,
+    pub scooters: f32,
+    pub parts: f32,
+    pub advertising: f32,
+    /// Rent, storage, wages, insurance, opening shops and moving scooters between them.
+    pub operating: f32,
+    /// Interest on loans and overdraft fees. Borrowing and paying it back isn't income or a cost.
+    pub financing: f32,
+    pub deployed: u32,
+    pub rented: u32,
+    pub broken: u32,
+}
+
+impl Report {
+    /// Everything from the start of `first_day` to the end of `last_day`.
+    pub fn new<'a>(
+        ledger: &Ledger,
+        records: impl IntoIterator<Item = &'a DayRecord>,
+        first_day: u32,
+        last_day: u32,
+    ) -> Self {
+        let days = first_day..=last_day;
+        let total = |categories: &[Category]| -> f32 {
+            ledger.transactions().iter()
+                .filter(|transaction| days.contains(&transaction.day) && categories.contains(&transaction.category))
+                .map(|transaction| transaction.amount)
+                .sum()
+        };
+        let mut report = Self {
+            first_day,
+            last_day,
+            revenue: total(&[Category::Rentals]),
+            scooters: -total(&[Category::ScooterPurchases, Category::ScooterSales]),
+            parts: -total(&[Category::Parts]),
+            advertising: -total(&[Category::Advertising]),
+            operating: -total(&[
+                Category::Rent, Category::Storage, Category::Wages, Category::Insurance,
+                Category::ShopOpenings, Category::Transfers,
+            ]),
+            financing: -total(&[Category::Interest, Category::OverdraftFees]),
+            deployed: 0,
+            rented: 0,
+            broken: 0,
+        };
+        for record in records.into_iter().filter(|record| days.contains(&record.day)) {
+            report.deployed += record.deployed().iter().map(|(_, num)| num).sum::<u32>();
+            report.rented += record.rented();
+            report.broken += record.broken_scooters();
+        }
+        report
+    }
+
+    pub fn costs(&self) -> f32 { self.scooters + self.parts + self.advertising + self.operating + self.financing }
+    pub fn net_income(&self) -> f32 { self.revenue - self.costs() }
+
+    /// The share of the periods deployed scooters spent out on rental.
+    pub fn utilization(&self) -> f32 {
+        let available = self.deployed * Period::ALL.len() as u32;
+        if available == 0 { 0.0 } else { self.rented as f32 / available as f32 }
+    }
+
+    /// The share of rentals that ended with a broken scooter.
+    pub fn breakage_rate(&self) -> f32 {
+        if self.rented == 0 { 0.0 } else { self.broken as f32 / self.rented as f32 }
+    }
+}
+
+#[cfg(test)]
+mod report_tests {
+    use crate::business::Receipt;
+    use crate::history::DayRecord;
+    use crate::ledger::{Category, Ledger};
+    use crate::location::Locations;
+    use crate::period::Periods;
+    use crate::report::Report;
+    use crate::weather::{Season, Temperature, WeatherType};
+
+    fn record(day: u32, deployed: u32, rented: u32, broken: u32) -> DayRecord {
+        DayRecord {
+            day,
+            prices: Locations::all(Periods::all(10.0)),
+            receipts: vec![Receipt::new(deployed, rented, rented as f32 * 10.0, broken)],
+            actions: Vec::new(),
+            weather: WeatherType::Sunny,
+            temperature: Temperature::Warm,
+            season: Season::Spring,
+            forecast: WeatherType::Sunny,
+        }
+    }
+
+    #[test]
+    fn income_statement_for_the_days_asked_for() {
+        let mut ledger = Ledger::opening(1, 100.0);
+        ledger.record(1, Category::Rentals, 80.0);
+        ledger.record(1, Category::ScooterPurchases, -50.0);
+        ledger.record(2, Category::Rentals, 40.0);
+        ledger.record(2, Category::ScooterSales, 20.0);
+        ledger.record(2, Category::Advertising, -6.0);
+        ledger.record(2, Category::Rent, -10.0);
+        let records = [record(1, 10, 8, 1), record(2, 5, 4, 0)];
+
+        let day = Report::new(&ledger, &records, 2, 2);
+        assert_eq!(day.revenue, 40.0);
+        assert_eq!(day.scooters, -20.0);
+        assert_eq!(day.costs(), -4.0);
+        assert_eq!(day.net_income(), 44.0);
+        assert_eq!(day.deployed, 5);
+
+        let both = Report::new(&ledger, &records, 1, 2);
+        assert_eq!(both.net_income(), 74.0);
+        assert_eq!(both.rented, 12);
+        assert_eq!(both.breakage_rate(), 1.0 / 12.0);
+        assert_eq!(both.utilization(), 12.0 / 60.0);
+    }
+
+    #[test]
+    fn quiet_days_have_no_rates() {
+        let report = Report::new(&Ledger::default(), &[], 1, 3);
+        assert_eq!(report.utilization(), 0.0);
+        assert_eq!(report.breakage_rate(), 0.0);
+        assert_eq!(report.net_income(), 0.0);
+    }
+}
@@ -0,0 +1,12 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The random number generator every part of the simulation draws from.
+///
+/// It is serializable so that a saved game carries on with exactly the same
+/// sequence of random numbers it would have had without stopping.
+pub type GameRng = ChaCha8Rng;
+
+pub fn seeded(seed: u64) -> GameRng {
+    GameRng::seed_from_u64(seed)
+}
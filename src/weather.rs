@@ -1,9 +1,28 @@
-use rand::{random};
-use rand::seq::SliceRandom;
-use crate::{DAYS_PER_SEASON, FORECAST_ACCURACY};
+use std::fmt;
+use std::str::FromStr;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::config::WeatherConfig;
+use crate::period::{Period, Periods};
 
-#[derive(PartialEq, Debug, Clone, Copy)]
-pub(crate) enum WeatherType {
+/// A weather word that isn't one the game knows about.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ParseWeatherError {
+    kind: &'static str,
+    input: String,
+}
+
+impl fmt::Display for ParseWeatherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\"{}\" is not a kind of {}", self.input, self.kind)
+    }
+}
+
+impl std::error::Error for ParseWeatherError {}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherType {
     Sunny,
     Cloudy,
     Rainy,
@@ -12,7 +31,11 @@ pub(crate) enum WeatherType {
 }
 
 impl WeatherType {
-    pub(crate) fn describe(&self) -> String {
+    pub const ALL: [WeatherType; 5] = [
+        WeatherType::Sunny, WeatherType::Cloudy, WeatherType::Rainy, WeatherType::Stormy, WeatherType::Snowy,
+    ];
+
+    pub fn describe(&self) -> String {
         match self {
             WeatherType::Sunny => "sunny".to_string(),
             WeatherType::Cloudy => "cloudy".to_string(),
@@ -22,20 +45,35 @@ impl WeatherType {
         }
     }
 
-    pub(crate) fn from_str(desc: &str) -> WeatherType {
+    /// What this weather comes down as at `celsius`: rain is snow once it's
+    /// freezing, and snow is rain above that.
+    pub fn at(&self, celsius: f32) -> WeatherType {
+        match self {
+            WeatherType::Rainy if celsius <= 0.0 => WeatherType::Snowy,
+            WeatherType::Snowy if celsius > 0.0 => WeatherType::Rainy,
+            weather => *weather,
+        }
+    }
+}
+
+impl FromStr for WeatherType {
+    type Err = ParseWeatherError;
+
+    fn from_str(desc: &str) -> Result<Self, Self::Err> {
         match desc {
-            "sunny" => WeatherType::Sunny,
-            "cloudy" => WeatherType::Cloudy,
-            "rainy" => WeatherType::Rainy,
-            "stormy" => WeatherType::Stormy,
-            "snowy" => WeatherType::Snowy,
-            _ => WeatherType::Sunny,
+            "sunny" => Ok(WeatherType::Sunny),
+            "cloudy" => Ok(WeatherType::Cloudy),
+            "rainy" => Ok(WeatherType::Rainy),
+            "stormy" => Ok(WeatherType::Stormy),
+            "snowy" => Ok(WeatherType::Snowy),
+            _ => Err(ParseWeatherError { kind: "weather", input: desc.to_string() }),
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Ord, PartialOrd)]
-pub(crate) enum Temperature {
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Ord, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum Temperature {
     Scorching,
     Hot,
     Warm,
@@ -45,7 +83,34 @@ pub(crate) enum Temperature {
 }
 
 impl Temperature {
-    pub(crate) fn describe(&self) -> String {
+    pub const ALL: [Temperature; 6] = [
+        Temperature::Scorching, Temperature::Hot, Temperature::Warm, Temperature::Cool, Temperature::Cold,
+        Temperature::Freezing,
+    ];
+
+    /// The band a reading in °C falls in.
+    pub fn from_celsius(celsius: f32) -> Self {
+        if celsius >= 35.0 { Temperature::Scorching }
+        else if celsius >= 27.0 { Temperature::Hot }
+        else if celsius >= 19.0 { Temperature::Warm }
+        else if celsius >= 10.0 { Temperature::Cool }
+        else if celsius >= 0.0 { Temperature::Cold }
+        else { Temperature::Freezing }
+    }
+
+    /// A reading in °C from the middle of the band.
+    pub fn typical_celsius(&self) -> f32 {
+        match self {
+            Temperature::Scorching => 38.0,
+            Temperature::Hot => 31.0,
+            Temperature::Warm => 23.0,
+            Temperature::Cool => 14.5,
+            Temperature::Cold => 5.0,
+            Temperature::Freezing => -5.0,
+        }
+    }
+
+    pub fn describe(&self) -> String {
         match self {
             Temperature::Scorching => "scorching".to_string(),
             Temperature::Hot => "hot".to_string(),
@@ -55,33 +120,51 @@ impl Temperature {
             Temperature::Freezing => "freezing".to_string(),
         }
     }
+}
+
+impl FromStr for Temperature {
+    type Err = ParseWeatherError;
 
-    pub(crate) fn from_str(temperature: &str) -> Temperature {
-        match temperature {
-            "scorching" => Temperature::Scorching,
-            "hot" => Temperature::Hot,
-            "warm" => Temperature::Warm,
-            "cool" => Temperature::Cool,
-            "cold" => Temperature::Cold,
-            "freezing" => Temperature::Freezing,
-            _ => Temperature::Warm,
+    fn from_str(desc: &str) -> Result<Self, Self::Err> {
+        match desc {
+            "scorching" => Ok(Temperature::Scorching),
+            "hot" => Ok(Temperature::Hot),
+            "warm" => Ok(Temperature::Warm),
+            "cool" => Ok(Temperature::Cool),
+            "cold" => Ok(Temperature::Cold),
+            "freezing" => Ok(Temperature::Freezing),
+            _ => Err(ParseWeatherError { kind: "temperature", input: desc.to_string() }),
         }
     }
 }
 
-pub(crate) enum ForecastTime { Today, Tomorrow }
+/// How many days ahead the forecast goes.
+pub const FORECAST_DAYS: usize = 7;
+
+pub enum ForecastTime { Today, Tomorrow, InDays(usize) }
 
 impl ForecastTime {
-    pub(crate) fn describe(&self) -> String {
+    pub fn describe(&self) -> String {
         match self {
             ForecastTime::Today => "today".to_string(),
             ForecastTime::Tomorrow => "tomorrow".to_string(),
+            ForecastTime::InDays(days) => format!("in {} days", days),
+        }
+    }
+
+    /// How many days from today, at most `FORECAST_DAYS`.
+    fn days_ahead(&self) -> usize {
+        match self {
+            ForecastTime::Today => 0,
+            ForecastTime::Tomorrow => 1,
+            ForecastTime::InDays(days) => (*days).min(FORECAST_DAYS),
         }
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Copy, Clone, Ord, PartialOrd)]
-pub(crate) enum Season {
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Copy, Clone, Ord, PartialOrd)]
+#[serde(rename_all = "lowercase")]
+pub enum Season {
     Spring,
     Summer,
     Fall,
@@ -89,7 +172,16 @@ pub(crate) enum Season {
 }
 
 impl Season {
-    pub(crate) fn describe(&self) -> String {
+    pub fn next(&self) -> Season {
+        match self {
+            Season::Spring => Season::Summer,
+            Season::Summer => Season::Fall,
+            Season::Fall => Season::Winter,
+            Season::Winter => Season::Spring,
+        }
+    }
+
+    pub fn describe(&self) -> String {
         match self {
             Season::Spring => "spring".to_string(),
             Season::Summer => "summer".to_string(),
@@ -97,149 +189,691 @@ impl Season {
             Season::Winter => "winter".to_string(),
         }
     }
+}
 
-    pub(crate) fn from_str(season: &str) -> Season {
-        match season {
-            "spring" => Season::Spring,
-            "summer" => Season::Summer,
-            "fall" => Season::Fall,
-            "winter" => Season::Winter,
-            _ => Season::Spring,
+impl FromStr for Season {
+    type Err = ParseWeatherError;
+
+    fn from_str(desc: &str) -> Result<Self, Self::Err> {
+        match desc {
+            "spring" => Ok(Season::Spring),
+            "summer" => Ok(Season::Summer),
+            "fall" => Ok(Season::Fall),
+            "winter" => Ok(Season::Winter),
+            _ => Err(ParseWeatherError { kind: "season", input: desc.to_string() }),
         }
     }
 }
 
-impl Season {
-    fn temperature_choice(&self) -> Temperature {
-        let mut vals: Vec<Temperature> = Vec::new();
+/// The range each reading takes under a kind of sky, and how much warmer
+/// or colder than the season's usual it runs.
+struct Sky {
+    wind_kph: (f32, f32),
+    rain_mm: (f32, f32),
+    cloud_cover: (f32, f32),
+    warmth: f32,
+}
+
+impl WeatherType {
+    fn sky(&self) -> Sky {
         match self {
-            Season::Spring => {
-                vals.push(Temperature::Cool);
-                vals.push(Temperature::Warm);
-                vals.push(Temperature::Hot);
-            },
-            Season::Summer => {
-                vals.push(Temperature::Warm);
-                vals.push(Temperature::Hot);
-                vals.push(Temperature::Scorching);
-            },
-            Season::Fall => {
-                vals.push(Temperature::Warm);
-                vals.push(Temperature::Cool);
-                vals.push(Temperature::Cold);
-            },
-            Season::Winter => {
-                vals.push(Temperature::Cool);
-                vals.push(Temperature::Cold);
-                vals.push(Temperature::Freezing);
-            },
-        }
-        vals.choose(&mut rand::thread_rng()).copied().unwrap()
-    }
-
-    fn weather_choice(&self) -> WeatherType {
-        let rnd = random::<f32>();
-        if rnd < 0.3 { WeatherType::Sunny }
-        else if rnd < 0.6 { WeatherType::Cloudy }
-        else if rnd < 0.8 {
-            if *self == Season::Winter { WeatherType::Snowy }
-            else { WeatherType::Rainy }
-        }
-        else { WeatherType::Stormy }
-    }
-}
-
-#[derive(Clone, Debug, Copy)]
-pub(crate) struct Weather {
+            WeatherType::Sunny => Sky { wind_kph: (0.0, 20.0), rain_mm: (0.0, 0.0), cloud_cover: (0.0, 0.4), warmth: 3.0 },
+            WeatherType::Cloudy => Sky { wind_kph: (5.0, 30.0), rain_mm: (0.0, 0.8), cloud_cover: (0.5, 1.0), warmth: 0.0 },
+            WeatherType::Rainy => Sky { wind_kph: (5.0, 40.0), rain_mm: (1.0, 15.0), cloud_cover: (0.8, 1.0), warmth: -2.0 },
+            WeatherType::Stormy => Sky { wind_kph: (50.0, 100.0), rain_mm: (2.0, 40.0), cloud_cover: (0.9, 1.0), warmth: -3.0 },
+            WeatherType::Snowy => Sky { wind_kph: (0.0, 35.0), rain_mm: (1.0, 10.0), cloud_cover: (0.8, 1.0), warmth: -6.0 },
+        }
+    }
+}
+
+/// A season's usual temperature in °C, and the most a day strays from it either way.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct TemperatureRange {
+    pub mean: f32,
+    pub spread: f32,
+}
+
+impl TemperatureRange {
+    /// Days near the usual temperature are the most common.
+    fn sample(&self, rng: &mut impl Rng) -> f32 {
+        self.mean + self.spread * (rng.gen::<f32>() + rng.gen::<f32>() - 1.0)
+    }
+}
+
+/// What the weather measures on a day. The `Temperature` and `WeatherType`
+/// the rest of the game talks about are worked out from these.
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq)]
+pub struct Conditions {
+    pub celsius: f32,
+    pub wind_kph: f32,
+    /// Rain, or snow once it's freezing, over the day.
+    pub rain_mm: f32,
+    /// Share of the sky covered by cloud, from 0 to 1.
+    pub cloud_cover: f32,
+}
+
+impl Conditions {
+    /// Wind at least this strong makes it stormy.
+    pub const STORM_WIND_KPH: f32 = 50.0;
+    /// Less than this is only a drizzle, and doesn't count as rain.
+    pub const RAIN_MM: f32 = 1.0;
+    /// More cloud than this makes it cloudy.
+    pub const CLOUDY_COVER: f32 = 0.5;
+
+    /// Readings from the middle of the ranges for `weather` at `temperature`.
+    pub fn typical(weather: WeatherType, temperature: Temperature) -> Self {
+        let sky = weather.sky();
+        let middle = |(low, high): (f32, f32)| (low + high) / 2.0;
+        let celsius = match weather {
+            WeatherType::Snowy => temperature.typical_celsius().min(-1.0),
+            WeatherType::Rainy => temperature.typical_celsius().max(1.0),
+            _ => temperature.typical_celsius(),
+        };
+        Self { celsius, wind_kph: middle(sky.wind_kph), rain_mm: middle(sky.rain_mm), cloud_cover: middle(sky.cloud_cover) }
+    }
+
+    /// A day under `weather`'s kind of sky in a season with the given `temperatures`.
+    fn sample(weather: WeatherType, temperatures: &TemperatureRange, rng: &mut impl Rng) -> Self {
+        let sky = weather.sky();
+        let mut between = |(low, high): (f32, f32)| low + (high - low) * rng.gen::<f32>();
+        let wind_kph = between(sky.wind_kph);
+        let rain_mm = between(sky.rain_mm);
+        let cloud_cover = between(sky.cloud_cover);
+        let celsius = temperatures.sample(rng) + sky.warmth;
+        // Snow needs it to be freezing
+        let celsius = if weather == WeatherType::Snowy { celsius.min(-0.5) } else { celsius };
+        Self { celsius, wind_kph, rain_mm, cloud_cover }
+    }
+
+    pub fn fahrenheit(&self) -> f32 { self.celsius * 9.0 / 5.0 + 32.0 }
+
+    pub fn temperature(&self) -> Temperature { Temperature::from_celsius(self.celsius) }
+
+    pub fn weather_type(&self) -> WeatherType {
+        if self.wind_kph >= Self::STORM_WIND_KPH { WeatherType::Stormy }
+        else if self.rain_mm >= Self::RAIN_MM {
+            if self.celsius <= 0.0 { WeatherType::Snowy } else { WeatherType::Rainy }
+        }
+        else if self.cloud_cover > Self::CLOUDY_COVER { WeatherType::Cloudy }
+        else { WeatherType::Sunny }
+    }
+
+    pub fn describe(&self) -> String {
+        let rain = if self.rain_mm < Self::RAIN_MM {
+            "no rain".to_string()
+        } else if self.celsius <= 0.0 {
+            format!("{:.0} mm of snow", self.rain_mm)
+        } else {
+            format!("{:.0} mm of rain", self.rain_mm)
+        };
+        format!("{:.0}°C ({:.0}°F), wind {:.0} km/h, {}", self.celsius, self.fahrenheit(), self.wind_kph, rain)
+    }
+}
+
+/// One value for each `Season`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Seasons<T> {
+    pub spring: T,
+    pub summer: T,
+    pub fall: T,
+    pub winter: T,
+}
+
+impl<T: Copy> Seasons<T> {
+    pub fn get(&self, season: Season) -> T {
+        match season {
+            Season::Spring => self.spring,
+            Season::Summer => self.summer,
+            Season::Fall => self.fall,
+            Season::Winter => self.winter,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Season, T)> {
+        [
+            (Season::Spring, self.spring),
+            (Season::Summer, self.summer),
+            (Season::Fall, self.fall),
+            (Season::Winter, self.winter),
+        ].into_iter()
+    }
+}
+
+impl<T> Seasons<T> {
+    pub fn get_ref(&self, season: Season) -> &T {
+        match season {
+            Season::Spring => &self.spring,
+            Season::Summer => &self.summer,
+            Season::Fall => &self.fall,
+            Season::Winter => &self.winter,
+        }
+    }
+}
+
+/// The chance of each kind of weather, adding up to 1.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WeatherChances {
+    pub sunny: f32,
+    pub cloudy: f32,
+    pub rainy: f32,
+    pub stormy: f32,
+    pub snowy: f32,
+}
+
+impl WeatherChances {
+    pub fn get(&self, weather: WeatherType) -> f32 {
+        match weather {
+            WeatherType::Sunny => self.sunny,
+            WeatherType::Cloudy => self.cloudy,
+            WeatherType::Rainy => self.rainy,
+            WeatherType::Stormy => self.stormy,
+            WeatherType::Snowy => self.snowy,
+        }
+    }
+
+    fn from_fn(mut f: impl FnMut(WeatherType) -> f32) -> Self {
+        Self {
+            sunny: f(WeatherType::Sunny),
+            cloudy: f(WeatherType::Cloudy),
+            rainy: f(WeatherType::Rainy),
+            stormy: f(WeatherType::Stormy),
+            snowy: f(WeatherType::Snowy),
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        let chances = WeatherType::ALL.map(|weather| self.get(weather));
+        chances.iter().all(|chance| *chance >= 0.0) && (chances.iter().sum::<f32>() - 1.0).abs() < 0.001
+    }
+
+    pub fn choose(&self, rng: &mut impl Rng) -> WeatherType {
+        let mut rnd = rng.gen::<f32>();
+        for weather in WeatherType::ALL {
+            rnd -= self.get(weather);
+            if rnd < 0.0 {
+                return weather;
+            }
+        }
+        // Rounding left a sliver over; give it to the last kind of weather that can happen
+        WeatherType::ALL.into_iter().rev().find(|weather| self.get(*weather) > 0.0).unwrap_or(WeatherType::Sunny)
+    }
+}
+
+/// What the weather is likely to do next, given what it's doing now: a
+/// Markov chain with a row of chances for each kind of weather.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Transitions {
+    pub sunny: WeatherChances,
+    pub cloudy: WeatherChances,
+    pub rainy: WeatherChances,
+    pub stormy: WeatherChances,
+    pub snowy: WeatherChances,
+}
+
+impl Transitions {
+    pub fn row(&self, from: WeatherType) -> &WeatherChances {
+        match from {
+            WeatherType::Sunny => &self.sunny,
+            WeatherType::Cloudy => &self.cloudy,
+            WeatherType::Rainy => &self.rainy,
+            WeatherType::Stormy => &self.stormy,
+            WeatherType::Snowy => &self.snowy,
+        }
+    }
+
+    pub fn next(&self, from: WeatherType, rng: &mut impl Rng) -> WeatherType {
+        self.row(from).choose(rng)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        WeatherType::ALL.iter().all(|from| self.row(*from).is_valid())
+    }
+
+    /// How often each kind of weather turns up over a long run, found by
+    /// stepping the chain on from an even spread until it settles.
+    pub fn stationary(&self) -> WeatherChances {
+        let mut chances = WeatherChances::from_fn(|_| 1.0 / WeatherType::ALL.len() as f32);
+        for _ in 0..1000 {
+            chances = WeatherChances::from_fn(|to| {
+                WeatherType::ALL.iter().map(|from| chances.get(*from) * self.row(*from).get(to)).sum()
+            });
+        }
+        chances
+    }
+}
+
+/// What the player is told a coming day will be like, and the chance it's right.
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq)]
+pub struct Forecast {
+    pub conditions: Conditions,
+    pub confidence: f32,
+}
+
+impl Forecast {
+    pub fn weather(&self) -> WeatherType { self.conditions.weather_type() }
+    pub fn temperature(&self) -> Temperature { self.conditions.temperature() }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Copy, PartialEq)]
+pub struct Weather {
     current: WeatherType,
-    forecast: WeatherType,
-    temperature: Temperature,
+    /// How today's weather turns out over the day, starting from `current`.
+    periods: Periods<WeatherType>,
+    /// Today's readings, which `current` was worked out from.
+    conditions: Conditions,
     season: Season,
     days_of_season: u8,
+    /// The weather really coming over the next `FORECAST_DAYS` days, starting tomorrow.
+    upcoming: [Conditions; FORECAST_DAYS],
+    /// What the player is told about the same days, less sure the further off they are.
+    forecasts: [Forecast; FORECAST_DAYS],
+    /// Comes from the game config rather than the save; see `with_config`.
+    #[serde(skip)]
+    config: WeatherConfig,
+}
+
+impl Default for Weather {
+    fn default() -> Self { Self::new(WeatherConfig::default()) }
 }
 
 impl Weather {
-    pub(crate) fn new() -> Weather {
+    /// Sunny and as warm as the climate's spring usually is today, and for the
+    /// whole week ahead until `new_outlook` says otherwise.
+    pub fn new(config: WeatherConfig) -> Weather {
+        let spring = config.climate.temperatures.spring.mean;
+        let typical = Conditions::typical(WeatherType::Sunny, Temperature::from_celsius(spring));
+        let conditions = Conditions { celsius: spring, ..typical };
         Weather {
             current: WeatherType::Sunny,
-            forecast: WeatherType::Sunny,
-            temperature: Temperature::Warm,
+            periods: Periods::all(WeatherType::Sunny),
+            conditions,
             season: Season::Spring,
             days_of_season: 0,
+            upcoming: [conditions; FORECAST_DAYS],
+            forecasts: [Forecast { conditions, confidence: 1.0 }; FORECAST_DAYS],
+            config,
         }
     }
 
-    pub(crate) fn with_curent_weather(mut self, current: &str) -> Self
-    { self.current = WeatherType::from_str(current); self }
-    pub(crate) fn with_forecast(mut self, forecast: &str) -> Self
-    { self.forecast = WeatherType::from_str(forecast); self }
-    pub(crate) fn with_temperature(mut self, temperature: &str) -> Self
-    { self.temperature = Temperature::from_str(temperature); self }
-    pub(crate) fn with_season(mut self, season: &str) -> Self
-    { self.season = Season::from_str(season); self }
-    pub(crate) fn with_days_of_season(mut self, days: u8) -> Self
+    pub fn with_config(mut self, config: WeatherConfig) -> Self
+    { self.config = config; self }
+    /// Sets the weather for the whole of today.
+    pub fn with_current_weather(mut self, current: WeatherType) -> Self
+    { self.current = current; self.periods = Periods::all(current); self }
+    pub fn with_period_weather(mut self, periods: Periods<WeatherType>) -> Self
+    { self.current = periods.morning; self.periods = periods; self }
+    /// Today's readings, with the weather they make lasting all day.
+    pub fn with_conditions(mut self, conditions: Conditions) -> Self
+    { self.conditions = conditions; self.with_current_weather(conditions.weather_type()) }
+    /// Tomorrow's weather, forecast for certain.
+    pub fn with_forecast(mut self, forecast: WeatherType) -> Self {
+        self.upcoming[0] = Conditions::typical(forecast, self.upcoming[0].temperature());
+        self.forecasts[0] = Forecast { conditions: self.upcoming[0], confidence: 1.0 };
+        self
+    }
+    /// Sets today's temperature to the middle of its band.
+    pub fn with_temperature(mut self, temperature: Temperature) -> Self
+    { self.conditions.celsius = temperature.typical_celsius(); self }
+    pub fn with_season(mut self, season: Season) -> Self
+    { self.season = season; self }
+    pub fn with_days_of_season(mut self, days: u8) -> Self
     { self.days_of_season = days; self }
 
-    pub(crate) fn get_temperature(self) -> Temperature { self.temperature }
-    pub(crate) fn get_current(self) -> WeatherType { self.current }
-    pub(crate) fn get_forecast(self) -> WeatherType { self.forecast }
-    pub(crate) fn get_season(self) -> Season { self.season }
-    pub(crate) fn get_days_of_season(self) -> u8 { self.days_of_season }
-
-    // TODO: Create tests
-    pub(crate) fn new_day(&mut self) {
-        // Current
-        if random::<f32>() > FORECAST_ACCURACY {
-            // Forecast sucked, get new one
-            self.new_forecast();
-        }
-        self.current = self.forecast.clone();
-        self.temperature = self.season.temperature_choice();
-        // Forecast
-        self.new_forecast();
+    pub fn get_temperature(self) -> Temperature { self.conditions.temperature() }
+    pub fn get_conditions(self) -> Conditions { self.conditions }
+    pub fn get_current(self) -> WeatherType { self.current }
+    pub fn get_period(self, period: Period) -> WeatherType { self.periods.get(period) }
+    pub fn get_periods(self) -> Periods<WeatherType> { self.periods }
+    /// The weather forecast for tomorrow.
+    pub fn get_forecast(self) -> WeatherType { self.forecasts[0].weather() }
+    /// The forecast for each of the next `FORECAST_DAYS` days, starting tomorrow.
+    pub fn get_forecasts(self) -> [Forecast; FORECAST_DAYS] { self.forecasts }
+    pub fn get_season(self) -> Season { self.season }
+    pub fn get_days_of_season(self) -> u8 { self.days_of_season }
+
+    /// Work out the week ahead afresh, carrying on from today's weather.
+    pub fn new_outlook(&mut self, rng: &mut impl Rng) {
+        let mut weather = self.current;
+        for ahead in 0..FORECAST_DAYS {
+            self.upcoming[ahead] = self.day_after(weather, ahead + 1, rng);
+            weather = self.upcoming[ahead].weather_type();
+        }
+        self.new_forecasts(rng);
+    }
+
+    pub fn new_day(&mut self, rng: &mut impl Rng) {
+        // Today is what was coming tomorrow
+        self.conditions = self.upcoming[0];
+        self.current = self.conditions.weather_type();
         // Days Tracking
         self.days_of_season += 1;
-        if self.days_of_season > DAYS_PER_SEASON {
+        if self.days_of_season > self.config.climate.season_days.get(self.season) {
             self.days_of_season = 1;
-            self.season = match self.season {
-                Season::Spring => Season::Summer,
-                Season::Summer => Season::Fall,
-                Season::Fall => Season::Winter,
-                Season::Winter => Season::Spring,
-            }
+            self.season = self.season.next();
         }
+        self.new_periods(rng);
+        // Another day comes into view at the end of the week
+        self.upcoming.rotate_left(1);
+        let last = self.upcoming[FORECAST_DAYS - 2].weather_type();
+        self.upcoming[FORECAST_DAYS - 1] = self.day_after(last, FORECAST_DAYS, rng);
+        self.new_forecasts(rng);
     }
 
-    // TODO: Create tests
-    pub(crate) fn describe(&self, forecast_time: ForecastTime) -> String {
-        format!("It {} a {} {} {} day, {}.",
-                match forecast_time {
-                    ForecastTime::Today => "is",
-                    ForecastTime::Tomorrow => "might be",
-                },
-                self.temperature.describe(),
-                match forecast_time {
-                    ForecastTime::Today => self.current.describe(),
-                    ForecastTime::Tomorrow => self.forecast.describe(),
-                },
-                self.season.describe(),
+    pub fn describe(&self, forecast_time: ForecastTime) -> String {
+        let days_ahead = forecast_time.days_ahead();
+        if days_ahead == 0 {
+            let mut changes = String::new();
+            let mut last = self.current;
+            for (period, weather) in self.periods.iter() {
+                if weather != last {
+                    changes.push_str(&format!(" Turning {} by the {}.", weather.describe(), period.describe()));
+                    last = weather;
+                }
+            }
+            return format!("It is a {} {} {} day, today: {}.{}",
+                           self.get_temperature().describe(), self.current.describe(), self.season.describe(),
+                           self.conditions.describe(), changes);
+        }
+        let forecast = self.forecasts[days_ahead - 1];
+        format!("It might be a {} {} {} day, {}, around {:.0}°C ({:.0}°F). ({:.0}% sure)",
+                forecast.temperature().describe(),
+                forecast.weather().describe(),
+                self.season_in(days_ahead).describe(),
                 forecast_time.describe(),
+                forecast.conditions.celsius,
+                forecast.conditions.fahrenheit(),
+                forecast.confidence * 100.0,
         )
     }
 
-    // TODO: Create tests
-    fn new_forecast(&mut self)  {
-        self.forecast = self.season.weather_choice()
+    /// Each period keeps the weather of the one before, unless it changes
+    /// the way today's season does. It's no colder or warmer for it, so rain
+    /// and snow stay whichever today's temperature allows.
+    fn new_periods(&mut self, rng: &mut impl Rng) {
+        let mut weather = self.current;
+        for period in Period::ALL {
+            if period != Period::Morning && rng.gen::<f32>() < self.config.intraday_change {
+                weather = self.transitions().next(weather, rng).at(self.conditions.celsius);
+            }
+            *self.periods.get_mut(period) = weather;
+        }
     }
 
+    /// The weather `days_ahead` days from today, following on from the day before's `weather`.
+    fn day_after(&self, weather: WeatherType, days_ahead: usize, rng: &mut impl Rng) -> Conditions {
+        let season = self.season_in(days_ahead);
+        let climate = &self.config.climate;
+        let sky = climate.transitions.get_ref(season).next(weather, rng);
+        Conditions::sample(sky, climate.temperatures.get_ref(season), rng)
+    }
+
+    /// Forecast each coming day again. A forecast is right as often as its
+    /// confidence says, which falls away the further off the day is; when it's
+    /// wrong it's a guess that follows on from the day before's forecast.
+    fn new_forecasts(&mut self, rng: &mut impl Rng) {
+        let mut confidence = self.config.forecast_accuracy;
+        let mut previous = self.current;
+        for ahead in 0..FORECAST_DAYS {
+            let truth = self.upcoming[ahead];
+            let forecast = if rng.gen::<f32>() < confidence {
+                truth
+            } else {
+                self.day_after(previous, ahead + 1, rng)
+            };
+            self.forecasts[ahead] = Forecast { conditions: forecast, confidence };
+            previous = forecast.weather_type();
+            confidence *= self.config.forecast_decay;
+        }
+    }
+
+    /// The season it will be `days_ahead` days from today.
+    fn season_in(&self, days_ahead: usize) -> Season {
+        let season_days = |season: Season| self.config.climate.season_days.get(season).max(1) as usize;
+        let mut season = self.season;
+        let mut days = self.days_of_season as usize + days_ahead;
+        while days > season_days(season) {
+            days -= season_days(season);
+            season = season.next();
+        }
+        season
+    }
+
+    fn transitions(&self) -> &Transitions { self.config.climate.transitions.get_ref(self.season) }
 }
 
 #[cfg(test)]
 mod season_tests {
+    use crate::climate::Climate;
+    use crate::config::WeatherConfig;
+    use crate::period::{Period, Periods};
+    use crate::rng::{seeded, GameRng};
+    use crate::weather::{
+        Conditions, ForecastTime, Season, Temperature, TemperatureRange, Transitions, Weather, WeatherChances,
+        WeatherType, FORECAST_DAYS,
+    };
 
     #[test]
     fn choose_weather() {
-        ()
+        let transitions = Climate::Temperate.profile().transitions;
+        let mut rng = seeded(7);
+        for from in WeatherType::ALL {
+            for _ in 0..100 {
+                assert_ne!(transitions.winter.next(from, &mut rng), WeatherType::Rainy);
+                assert_ne!(transitions.summer.next(from, &mut rng), WeatherType::Snowy);
+            }
+        }
+    }
+
+    #[test]
+    fn default_transitions_settle_to_each_seasons_mix() {
+        let transitions = Climate::Temperate.profile().transitions;
+        let close = |a: WeatherChances, b: WeatherChances| {
+            WeatherType::ALL.iter().all(|weather| (a.get(*weather) - b.get(*weather)).abs() < 0.001)
+        };
+        for (_, season) in transitions.iter() {
+            assert!(season.is_valid());
+        }
+        let usual = WeatherChances { sunny: 0.3, cloudy: 0.3, rainy: 0.2, stormy: 0.2, snowy: 0.0 };
+        assert!(close(transitions.spring.stationary(), usual));
+        assert!(close(transitions.summer.stationary(), WeatherChances { sunny: 0.4, cloudy: 0.3, rainy: 0.15, stormy: 0.15, ..usual }));
+        assert!(close(transitions.winter.stationary(), WeatherChances { rainy: 0.0, snowy: 0.2, ..usual }));
+    }
+
+    #[test]
+    fn a_long_run_of_weather_matches_the_stationary_mix() {
+        let transitions = Climate::Temperate.profile().transitions.spring;
+        let stationary = transitions.stationary();
+        let mut rng = seeded(5);
+        let mut weather = WeatherType::Snowy;
+        let mut counts = [0; 5];
+        let days = 20_000;
+        for _ in 0..days {
+            weather = transitions.next(weather, &mut rng);
+            counts[WeatherType::ALL.iter().position(|kind| *kind == weather).unwrap()] += 1;
+        }
+        for (kind, count) in WeatherType::ALL.iter().zip(counts) {
+            assert!((count as f32 / days as f32 - stationary.get(*kind)).abs() < 0.02, "{:?}", kind);
+        }
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn weather_sticks_around() {
+        let transitions = Climate::Temperate.profile().transitions.spring;
+        let mut rng = seeded(9);
+        let mut weather = WeatherType::Sunny;
+        let (mut same, days) = (0, 1000);
+        for _ in 0..days {
+            let next = transitions.next(weather, &mut rng);
+            if next == weather {
+                same += 1;
+            }
+            weather = next;
+        }
+        // Drawing each day afresh would repeat the weather under 30% of the time
+        assert!(same as f32 / days as f32 > 0.45);
+    }
+
+    #[test]
+    fn sunny_days_run_warmer_than_stormy_ones() {
+        let mut rng = seeded(13);
+        let spring = TemperatureRange { mean: 19.0, spread: 9.0 };
+        let average = |weather: WeatherType, rng: &mut GameRng| {
+            (0..1000).map(|_| Conditions::sample(weather, &spring, rng).celsius).sum::<f32>() / 1000.0
+        };
+        assert!(average(WeatherType::Sunny, &mut rng) > average(WeatherType::Stormy, &mut rng) + 4.0);
+    }
+
+    #[test]
+    fn every_sky_reads_as_itself() {
+        let mut rng = seeded(23);
+        let winter = Climate::Temperate.profile().temperatures.winter;
+        for weather in WeatherType::ALL {
+            for temperature in Temperature::ALL {
+                assert_eq!(Conditions::typical(weather, temperature).weather_type(), weather);
+            }
+            for _ in 0..100 {
+                let conditions = Conditions::sample(weather, &TemperatureRange { mean: 20.0, ..winter }, &mut rng);
+                if weather != WeatherType::Rainy || conditions.celsius > 0.0 {
+                    assert_eq!(conditions.weather_type(), weather);
+                }
+            }
+        }
+        // Rain that falls below freezing comes down as snow
+        let sleet = Conditions { celsius: -2.0, ..Conditions::typical(WeatherType::Rainy, Temperature::Cold) };
+        assert_eq!(sleet.weather_type(), WeatherType::Snowy);
+    }
+
+    #[test]
+    fn temperatures_fall_into_bands() {
+        for temperature in Temperature::ALL {
+            assert_eq!(Temperature::from_celsius(temperature.typical_celsius()), temperature);
+        }
+        assert_eq!(Temperature::from_celsius(-0.1), Temperature::Freezing);
+        assert_eq!(Temperature::from_celsius(0.0), Temperature::Cold);
+        let conditions = Conditions { celsius: 100.0, ..Conditions::typical(WeatherType::Sunny, Temperature::Hot) };
+        assert_eq!(conditions.fahrenheit(), 212.0);
+    }
+
+    #[test]
+    fn parse_known_words() {
+        assert_eq!("snowy".parse::<WeatherType>(), Ok(WeatherType::Snowy));
+        assert_eq!("fall".parse::<Season>(), Ok(Season::Fall));
+        assert_eq!("freezing".parse::<Temperature>(), Ok(Temperature::Freezing));
+    }
+
+    #[test]
+    fn typos_are_errors() {
+        assert!("sunnny".parse::<WeatherType>().is_err());
+        assert!("autumn".parse::<Season>().is_err());
+        assert!("".parse::<Temperature>().is_err());
+    }
+
+    #[test]
+    fn weather_round_trips_through_ron() {
+        let weather = Weather::default()
+            .with_current_weather(WeatherType::Stormy)
+            .with_temperature(Temperature::Cold)
+            .with_season(Season::Winter);
+        let text = ron::to_string(&weather).unwrap();
+        assert!(text.contains("stormy"));
+        assert_eq!(ron::from_str::<Weather>(&text).unwrap(), weather);
+    }
+
+    #[test]
+    fn the_day_starts_with_the_current_weather() {
+        let mut rng = seeded(11);
+        let mut weather = Weather::default();
+        for _ in 0..30 {
+            weather.new_day(&mut rng);
+            assert_eq!(weather.get_period(Period::Morning), weather.get_current());
+        }
+    }
+
+    #[test]
+    fn steady_weather_never_changes_during_the_day() {
+        let config = WeatherConfig { intraday_change: 0.0, ..WeatherConfig::default() };
+        let mut rng = seeded(11);
+        let mut weather = Weather::new(config);
+        for _ in 0..30 {
+            weather.new_day(&mut rng);
+            assert_eq!(weather.get_periods(), Periods::all(weather.get_current()));
+        }
+    }
+
+    #[test]
+    fn periods_follow_the_new_season_and_the_temperature() {
+        let always = |to: WeatherType| {
+            let chances = WeatherChances::from_fn(|weather| if weather == to { 1.0 } else { 0.0 });
+            Transitions { sunny: chances, cloudy: chances, rainy: chances, stormy: chances, snowy: chances }
+        };
+        let mut config = WeatherConfig { intraday_change: 1.0, ..WeatherConfig::default() };
+        config.climate.transitions.spring = always(WeatherType::Stormy);
+        config.climate.transitions.summer = always(WeatherType::Cloudy);
+        config.climate.transitions.fall = always(WeatherType::Snowy);
+        let mut rng = seeded(23);
+
+        let spring_days = config.climate.season_days.spring;
+        let mut weather = Weather::new(config).with_days_of_season(spring_days).with_forecast(WeatherType::Sunny);
+        weather.new_day(&mut rng);
+        assert_eq!(weather.get_season(), Season::Summer);
+        assert_eq!(weather.get_period(Period::Midday), WeatherType::Cloudy);
+        assert_eq!(weather.get_period(Period::Night), WeatherType::Cloudy);
+
+        let mut weather = Weather::new(config).with_season(Season::Fall).with_forecast(WeatherType::Sunny);
+        weather.new_day(&mut rng);
+        assert!(weather.get_conditions().celsius > 0.0);
+        assert_eq!(weather.get_period(Period::Evening), WeatherType::Rainy);
+        assert_eq!(WeatherType::Rainy.at(-2.0), WeatherType::Snowy);
+    }
+
+    #[test]
+    fn same_seed_same_weather() {
+        let (mut rng_a, mut rng_b) = (seeded(3), seeded(3));
+        let (mut a, mut b) = (Weather::default(), Weather::default());
+        for _ in 0..30 {
+            a.new_day(&mut rng_a);
+            b.new_day(&mut rng_b);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn forecasts_grow_less_sure_further_ahead() {
+        let mut rng = seeded(17);
+        let mut weather = Weather::default();
+        weather.new_outlook(&mut rng);
+        let forecasts = weather.get_forecasts();
+        assert_eq!(forecasts[0].confidence, WeatherConfig::default().forecast_accuracy);
+        assert!(forecasts.windows(2).all(|pair| pair[1].confidence < pair[0].confidence));
+        assert!(weather.describe(ForecastTime::Tomorrow).ends_with("(70% sure)"));
+    }
+
+    #[test]
+    fn perfect_forecasts_come_true() {
+        let config = WeatherConfig { forecast_accuracy: 1.0, forecast_decay: 1.0, ..WeatherConfig::default() };
+        let mut rng = seeded(19);
+        let mut weather = Weather::new(config);
+        weather.new_outlook(&mut rng);
+        for _ in 0..30 {
+            let forecasts = weather.get_forecasts();
+            weather.new_day(&mut rng);
+            assert_eq!(weather.get_conditions(), forecasts[0].conditions);
+            assert_eq!(weather.get_current(), forecasts[0].weather());
+            assert_eq!(weather.get_forecasts()[..FORECAST_DAYS - 1], forecasts[1..]);
+        }
+    }
+
+    #[test]
+    fn the_forecast_looks_into_next_season() {
+        let weather = Weather::default().with_season(Season::Winter).with_days_of_season(5);
+        assert_eq!(weather.season_in(1), Season::Winter);
+        assert_eq!(weather.season_in(2), Season::Spring);
+        assert_eq!(weather.season_in(FORECAST_DAYS), Season::Spring);
+        assert_eq!(weather.season_in(FORECAST_DAYS + 1), Season::Summer);
+        assert!(weather.describe(ForecastTime::InDays(3)).contains("spring day, in 3 days"));
+    }
+}